@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use criterion::{ black_box, criterion_group, criterion_main, Criterion };
+use rl_examples::{
+    agents::{ agent::Agent, q::AgentQ },
+    environments::bandit::KArmedBandit,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ DenseStore, MemoryStore, StateActionStore, Store, StoreId },
+};
+
+const NUM_ACTIONS: usize = 10;
+
+// store keyed by formatted strings, kept here as a reference point for the typed stores
+struct StringStore {
+    store: HashMap<String, f64>,
+}
+
+impl StringStore {
+    fn generate_id(&self, state: String, action: Option<usize>) -> String {
+        match action {
+            Some(action) => format!("{}-{}", state, action),
+            None => state,
+        }
+    }
+
+    fn get_float(&self, key: &String) -> f64 {
+        *self.store.get(key).unwrap_or(&0.0)
+    }
+
+    fn store_float(&mut self, key: String, value: f64) {
+        self.store.insert(key, value);
+    }
+}
+
+fn string_store_greedy_scan(store: &mut StringStore) -> usize {
+    let mut max = f64::NEG_INFINITY;
+    let mut max_index = 0;
+    for i in 0..NUM_ACTIONS {
+        let id = store.generate_id("0".to_string(), Some(i));
+        let value = store.get_float(&id);
+        if value >= max {
+            max = value;
+            max_index = i;
+        }
+    }
+    let id = store.generate_id("0".to_string(), Some(max_index));
+    let value = store.get_float(&id);
+    store.store_float(id, value + 1.0);
+    max_index
+}
+
+fn typed_store_greedy_scan<S: StateActionStore<usize, usize>>(store: &mut S) -> usize {
+    let mut max = f64::NEG_INFINITY;
+    let mut max_index = 0;
+    for i in 0..NUM_ACTIONS {
+        let id = store.generate_id(0, Some(i));
+        let value = store.get_float(&id);
+        if value >= max {
+            max = value;
+            max_index = i;
+        }
+    }
+    let id = store.generate_id(0, Some(max_index));
+    store.increment_float(id, 1.0);
+    max_index
+}
+
+fn bench_greedy_scan(c: &mut Criterion) {
+    let mut group = c.benchmark_group("greedy_scan");
+    group.bench_function("string_store", |b| {
+        let mut store = StringStore { store: HashMap::new() };
+        b.iter(|| black_box(string_store_greedy_scan(&mut store)))
+    });
+    group.bench_function("memory_store", |b| {
+        let mut store: MemoryStore<StoreId<usize, usize>> = MemoryStore::new();
+        b.iter(|| black_box(typed_store_greedy_scan(&mut store)))
+    });
+    group.bench_function("dense_store", |b| {
+        let mut store = DenseStore::with_states_and_actions(vec![0], (0..NUM_ACTIONS).collect());
+        b.iter(|| black_box(typed_store_greedy_scan(&mut store)))
+    });
+    group.finish();
+}
+
+// a single run of the k_armed_bandit_epsilon_greedy binary
+fn bench_bandit_run(c: &mut Criterion) {
+    let num_steps = 1000;
+    c.bench_function("k_armed_bandit_epsilon_greedy_run", |b| {
+        b.iter(|| {
+            let mut agent = AgentQ::new(
+                KArmedBandit::new(NUM_ACTIONS),
+                EpsilonGreedySelector::new(0.1),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                MemoryStore::new()
+            );
+            let mut total_reward = 0.0;
+            for _ in 0..num_steps {
+                let action = agent.select_action();
+                let reward = agent.take_action(action);
+                agent.update_estimate(0, action, reward, false);
+                total_reward += reward;
+            }
+            black_box(total_reward)
+        })
+    });
+}
+
+criterion_group!(benches, bench_greedy_scan, bench_bandit_run);
+criterion_main!(benches);
@@ -1,8 +1,74 @@
+use crate::{ environment::Environment, store::StateActionStore };
+
 pub trait Agent {
-    fn select_action(&mut self) -> usize;
-    fn take_action(&mut self, action: usize) -> f64;
-    fn update_estimate(&mut self, state: String, action: usize, reward: f64, is_terminal: bool);
+    type State: State;
+    type Action: Action;
+    /// Environment the agent acts in, which answers the questions about the current state
+    type Environment: Environment<State = Self::State, Action = Self::Action>;
+    fn get_environment(&self) -> &Self::Environment;
+    fn select_action(&mut self) -> Self::Action;
+    fn take_action(&mut self, action: Self::Action) -> f64;
+    /// Learn from a step. Called after `take_action`, so the environment already holds the
+    /// state the step led to.
+    fn update_estimate(
+        &mut self,
+        state: Self::State,
+        action: Self::Action,
+        reward: f64,
+        is_terminal: bool
+    );
+    /// Current state of the environment the agent acts in
+    fn get_state(&self) -> Self::State {
+        self.get_environment().get_state()
+    }
+    fn is_terminal(&self) -> bool {
+        self.get_environment().is_terminal()
+    }
+    /// Whether the episode was cut off before reaching a terminal state.
+    /// The episode ends, but estimates may still bootstrap from the state it stopped in.
+    fn is_truncated(&self) -> bool {
+        false
+    }
+    /// Reset the environment to start a new episode. Learned estimates are kept.
+    fn reset(&mut self);
+    /// Whether the action is optimal in the current state, if the environment knows
+    fn is_optimal_action(&self, action: &Self::Action) -> Option<bool> {
+        self.get_environment().is_optimal_action(action)
+    }
+    /// Expected reward lost by taking the action instead of an optimal one, if the environment knows
+    fn regret(&self, action: &Self::Action) -> Option<f64> {
+        self.get_environment().regret(action)
+    }
+}
+
+///
+/// Count an action as taken in the current state of the environment, then take it
+///
+/// # Arguments
+///
+/// * `environment` - &mut T - environment to act in
+/// * `store_action_count` - &mut S - store for the number of times each state action pair was taken
+/// * `action` - T::Action - action to take
+///
+/// # Returns
+///
+/// * `f64` - reward of the step
+pub(crate) fn take_counted_action<T: Environment, S: StateActionStore<T::State, T::Action>>(
+    environment: &mut T,
+    store_action_count: &mut S,
+    action: T::Action
+) -> f64 {
+    let id = store_action_count.generate_id(environment.get_state(), Some(action.clone()));
+    store_action_count.increment_float(id, 1.0);
+    environment.step(action)
 }
 
 // state trait that should be hashable
 pub trait State: std::hash::Hash + Eq + Clone {}
+
+impl<T: std::hash::Hash + Eq + Clone> State for T {}
+
+// action trait that should be hashable
+pub trait Action: std::hash::Hash + Eq + Clone {}
+
+impl<T: std::hash::Hash + Eq + Clone> Action for T {}
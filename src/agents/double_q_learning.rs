@@ -0,0 +1,223 @@
+use rand::{ rngs::StdRng, Rng, SeedableRng };
+
+use crate::{ environment::Environment, store::StateActionStore };
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+/// Tabular Double Q-learning (off-policy TD control without maximization bias).
+///
+/// Two independent action value estimates are kept. On every step a fair coin picks one of
+/// them to update, using the action that is greedy under the updated estimate but valued by
+/// the other one: `Q1(s, a) += α (r + γ Q2(s', argmax_a' Q1(s', a')) - Q1(s, a))`.
+/// Actions are chosen by the selector from the sum of both estimates.
+pub struct AgentDoubleQLearning<
+    T: Environment,
+    U: Selector,
+    S: StateActionStore<T::State, T::Action>
+> {
+    environment: T,
+    selector: U,
+    q_stores: [S; 2],
+    /// sum of both estimates, kept up to date for the selector
+    q_sum_store: S,
+    store_action_count: S,
+    step_size: f64,
+    discount: f64,
+    rng: StdRng,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentDoubleQLearning<
+    T,
+    U,
+    S
+> {
+    ///
+    /// Create a new Double Q-learning agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - behavior policy, applied to the sum of both estimates
+    /// * `q_stores` - [S; 2] - stores for the two action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `step_size` - f64 - step size (alpha) of the update
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    pub fn new(
+        environment: T,
+        selector: U,
+        q_stores: [S; 2],
+        store_action_count: S,
+        step_size: f64,
+        discount: f64
+    ) -> AgentDoubleQLearning<T, U, S> {
+        AgentDoubleQLearning::with_rng(
+            environment,
+            selector,
+            q_stores,
+            store_action_count,
+            step_size,
+            discount,
+            StdRng::from_entropy()
+        )
+    }
+
+    ///
+    /// Create a new Double Q-learning agent whose choice of estimate to update is reproducible
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - behavior policy, applied to the sum of both estimates
+    /// * `q_stores` - [S; 2] - stores for the two action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `step_size` - f64 - step size (alpha) of the update
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    /// * `seed` - u64 - seed for the coin that picks the estimate to update
+    pub fn with_seed(
+        environment: T,
+        selector: U,
+        q_stores: [S; 2],
+        store_action_count: S,
+        step_size: f64,
+        discount: f64,
+        seed: u64
+    ) -> AgentDoubleQLearning<T, U, S> {
+        AgentDoubleQLearning::with_rng(
+            environment,
+            selector,
+            q_stores,
+            store_action_count,
+            step_size,
+            discount,
+            StdRng::seed_from_u64(seed)
+        )
+    }
+
+    fn with_rng(
+        environment: T,
+        selector: U,
+        q_stores: [S; 2],
+        store_action_count: S,
+        step_size: f64,
+        discount: f64,
+        rng: StdRng
+    ) -> AgentDoubleQLearning<T, U, S> {
+        // the sum starts from whatever the stores were initialised with
+        let mut q_sum_store = S::new();
+        for q_store in q_stores.iter() {
+            for (id, value) in q_store.entries() {
+                q_sum_store.increment_float(id, value);
+            }
+        }
+        AgentDoubleQLearning {
+            environment,
+            selector,
+            q_stores,
+            q_sum_store,
+            store_action_count,
+            step_size,
+            discount,
+            rng,
+            total_actions_taken: 0,
+        }
+    }
+
+    ///
+    /// Get the action value estimate of one of the two stores
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - usize - 0 or 1, the store to read
+    /// * `state` - T::State - state
+    /// * `action` - T::Action - action
+    pub fn get_q_estimate_of(&self, index: usize, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_stores[index].generate_id(state, Some(action));
+        self.q_stores[index].get_float(&id)
+    }
+
+    /// Average of the two action value estimates
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_sum_store.generate_id(state, Some(action));
+        self.q_sum_store.get_float(&id) / 2.0
+    }
+
+    ///
+    /// Value under one store of the action that is greedy under the other store, in the
+    /// current state of the environment
+    ///
+    /// # Arguments
+    ///
+    /// * `greedy_index` - usize - store that picks the action
+    fn double_q_estimate(&self, greedy_index: usize) -> f64 {
+        let state = self.environment.get_state();
+        let mut max = f64::NEG_INFINITY;
+        let mut max_action = None;
+        for action in self.environment.get_actions() {
+            let q_estimate = self.get_q_estimate_of(greedy_index, state.clone(), action.clone());
+            if q_estimate >= max {
+                max = q_estimate;
+                max_action = Some(action);
+            }
+        }
+        match max_action {
+            Some(action) => self.get_q_estimate_of(1 - greedy_index, state, action),
+            None => 0.0,
+        }
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentDoubleQLearning<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        self.selector.select_action(
+            &mut self.environment,
+            &self.q_sum_store,
+            &self.store_action_count
+        )
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Updates one of the two estimates picked at random, evaluating its greedy action in the
+    /// next state with the other
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        let index = if self.rng.gen::<bool>() { 0 } else { 1 };
+        // terminal states have no future value to bootstrap from
+        let target = if is_terminal {
+            reward
+        } else {
+            reward + self.discount * self.double_q_estimate(index)
+        };
+        let id = self.q_stores[index].generate_id(state, Some(action));
+        let current_q_estimate = self.q_stores[index].get_float(&id);
+        let change = self.step_size * (target - current_q_estimate);
+        self.q_stores[index].store_float(id.clone(), current_q_estimate + change);
+        self.q_sum_store.increment_float(id, change);
+    }
+
+    fn reset(&mut self) {
+        self.environment.reset();
+    }
+}
@@ -0,0 +1,298 @@
+use rand::{ rngs::StdRng, Rng, SeedableRng };
+use rustc_hash::FxHashMap;
+
+use crate::{ environment::Environment, store::StateActionStore };
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+#[derive(Debug, Clone, Copy)]
+pub struct DynaConfig {
+    /// number of simulated updates from the model after every real step
+    pub planning_steps: usize,
+    /// step size (alpha) of the update
+    pub step_size: f64,
+    /// discount factor (gamma) of future rewards
+    pub discount: f64,
+    /// weight (kappa) of the `kappa * sqrt(steps since last tried)` bonus added to simulated
+    /// rewards, which turns Dyna-Q into Dyna-Q+
+    pub exploration_bonus: Option<f64>,
+}
+
+impl Default for DynaConfig {
+    fn default() -> Self {
+        DynaConfig {
+            planning_steps: 5,
+            step_size: 0.1,
+            discount: 0.95,
+            exploration_bonus: None,
+        }
+    }
+}
+
+/// Outcome last observed for a state action pair
+struct ModelEntry<S> {
+    reward: f64,
+    next_state: S,
+    is_terminal: bool,
+    /// real step at which the pair was last taken
+    last_visit: usize,
+}
+
+type Model<S, A> = FxHashMap<(S, A), ModelEntry<S>>;
+
+/// Tabular Dyna-Q and Dyna-Q+.
+///
+/// Every real step is learned from with Q-learning and recorded in a deterministic model
+/// `(s, a) -> (r, s')`. The model is then replayed for `planning_steps` Q-learning updates
+/// of state action pairs drawn uniformly from the ones observed so far. Dyna-Q+ also lets
+/// planning try actions that were never taken, modelled as staying in place with no reward,
+/// and adds a bonus to simulated rewards that grows with the time since the pair was last
+/// taken, so it revisits parts of the environment that may have changed.
+pub struct AgentDynaQ<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> {
+    environment: T,
+    selector: U,
+    q_store: S,
+    store_action_count: S,
+    config: DynaConfig,
+    model: Model<T::State, T::Action>,
+    /// keys of the model, to draw planning updates from
+    observed: Vec<(T::State, T::Action)>,
+    /// actions available in every state seen so far
+    state_actions: FxHashMap<T::State, Vec<T::Action>>,
+    rng: StdRng,
+    total_actions_taken: usize,
+    total_updates: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentDynaQ<T, U, S> {
+    ///
+    /// Create a new Dyna-Q agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - behavior policy
+    /// * `q_store` - S - store for action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `config` - DynaConfig - planning steps, step size, discount and exploration bonus
+    pub fn new(
+        environment: T,
+        selector: U,
+        q_store: S,
+        store_action_count: S,
+        config: DynaConfig
+    ) -> AgentDynaQ<T, U, S> {
+        AgentDynaQ::with_rng(
+            environment,
+            selector,
+            q_store,
+            store_action_count,
+            config,
+            StdRng::from_entropy()
+        )
+    }
+
+    ///
+    /// Create a new Dyna-Q agent whose planning updates are reproducible
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - behavior policy
+    /// * `q_store` - S - store for action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `config` - DynaConfig - planning steps, step size, discount and exploration bonus
+    /// * `seed` - u64 - seed for drawing the pairs to plan with
+    pub fn with_seed(
+        environment: T,
+        selector: U,
+        q_store: S,
+        store_action_count: S,
+        config: DynaConfig,
+        seed: u64
+    ) -> AgentDynaQ<T, U, S> {
+        AgentDynaQ::with_rng(
+            environment,
+            selector,
+            q_store,
+            store_action_count,
+            config,
+            StdRng::seed_from_u64(seed)
+        )
+    }
+
+    fn with_rng(
+        environment: T,
+        selector: U,
+        q_store: S,
+        store_action_count: S,
+        config: DynaConfig,
+        rng: StdRng
+    ) -> AgentDynaQ<T, U, S> {
+        AgentDynaQ {
+            environment,
+            selector,
+            q_store,
+            store_action_count,
+            config,
+            model: FxHashMap::default(),
+            observed: Vec::new(),
+            state_actions: FxHashMap::default(),
+            rng,
+            total_actions_taken: 0,
+            total_updates: 0,
+        }
+    }
+
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_store.generate_id(state, Some(action));
+        self.q_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+
+    /// Number of action value updates made so far, from real and simulated experience
+    pub fn get_total_updates(&self) -> usize {
+        self.total_updates
+    }
+
+    /// Number of state action pairs held by the model
+    pub fn get_model_size(&self) -> usize {
+        self.observed.len()
+    }
+
+    /// Largest action value estimate of a state seen before, 0 for unseen states
+    fn max_q_estimate(&self, state: &T::State) -> f64 {
+        match self.state_actions.get(state) {
+            Some(actions) =>
+                actions
+                    .iter()
+                    .map(|action| self.get_q_estimate(state.clone(), action.clone()))
+                    .fold(f64::NEG_INFINITY, f64::max),
+            None => 0.0,
+        }
+    }
+
+    fn update_q_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        next_state: &T::State,
+        is_terminal: bool
+    ) {
+        // terminal states have no future value to bootstrap from
+        let target = if is_terminal {
+            reward
+        } else {
+            reward + self.config.discount * self.max_q_estimate(next_state)
+        };
+        let id = self.q_store.generate_id(state, Some(action));
+        let current_q_estimate = self.q_store.get_float(&id);
+        let new_q_estimate = current_q_estimate + self.config.step_size * (target - current_q_estimate);
+        self.q_store.store_float(id, new_q_estimate);
+        self.total_updates += 1;
+    }
+
+    /// Remember the actions of the current state of the environment the first time it is seen
+    fn register_current_state(&mut self) {
+        let state = self.environment.get_state();
+        if self.state_actions.contains_key(&state) {
+            return;
+        }
+        let actions = self.environment.get_actions();
+        if self.config.exploration_bonus.is_some() {
+            // Dyna-Q+ may plan with actions that were never taken, as if they led nowhere
+            for action in actions.iter() {
+                self.record_outcome(state.clone(), action.clone(), 0.0, state.clone(), false, 0);
+            }
+        }
+        self.state_actions.insert(state, actions);
+    }
+
+    fn record_outcome(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        next_state: T::State,
+        is_terminal: bool,
+        last_visit: usize
+    ) {
+        let entry = ModelEntry {
+            reward,
+            next_state,
+            is_terminal,
+            last_visit,
+        };
+        let key = (state, action);
+        if self.model.insert(key.clone(), entry).is_none() {
+            self.observed.push(key);
+        }
+    }
+
+    /// Replay the model for the configured number of simulated updates
+    fn plan(&mut self) {
+        if self.observed.is_empty() {
+            return;
+        }
+        for _ in 0..self.config.planning_steps {
+            let index = self.rng.gen_range(0..self.observed.len());
+            let (state, action) = self.observed[index].clone();
+            let entry = &self.model[&(state.clone(), action.clone())];
+            let mut reward = entry.reward;
+            if let Some(bonus) = self.config.exploration_bonus {
+                let steps_since_visit = (self.total_actions_taken - entry.last_visit) as f64;
+                reward += bonus * steps_since_visit.sqrt();
+            }
+            let next_state = entry.next_state.clone();
+            let is_terminal = entry.is_terminal;
+            self.update_q_estimate(state, action, reward, &next_state, is_terminal);
+        }
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentDynaQ<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        self.selector.select_action(&mut self.environment, &self.q_store, &self.store_action_count)
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.register_current_state();
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Learns from the real step, records it in the model and then plans from the model
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        if !is_terminal {
+            self.register_current_state();
+        }
+        let next_state = self.environment.get_state();
+        self.update_q_estimate(state.clone(), action.clone(), reward, &next_state, is_terminal);
+        let time = self.total_actions_taken;
+        self.record_outcome(state, action, reward, next_state, is_terminal, time);
+        self.plan();
+    }
+
+    fn reset(&mut self) {
+        self.environment.reset();
+    }
+}
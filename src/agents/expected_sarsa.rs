@@ -0,0 +1,121 @@
+use crate::{ environment::Environment, store::StateActionStore };
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+/// Tabular Expected SARSA.
+///
+/// The action value of the state action pair is moved towards
+/// `r + discount * sum_a' pi(a' | s') Q(s', a')`, the expected value of the next state under
+/// the selector's action probabilities. This removes the variance SARSA gets from sampling `a'`.
+pub struct AgentExpectedSarsa<
+    T: Environment,
+    U: Selector,
+    S: StateActionStore<T::State, T::Action>
+> {
+    environment: T,
+    selector: U,
+    q_store: S,
+    store_action_count: S,
+    step_size: f64,
+    discount: f64,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentExpectedSarsa<
+    T,
+    U,
+    S
+> {
+    ///
+    /// Create a new Expected SARSA agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - policy that is followed and learned about
+    /// * `q_store` - S - store for action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `step_size` - f64 - step size (alpha) of the update
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    pub fn new(
+        environment: T,
+        selector: U,
+        q_store: S,
+        store_action_count: S,
+        step_size: f64,
+        discount: f64
+    ) -> AgentExpectedSarsa<T, U, S> {
+        AgentExpectedSarsa {
+            environment,
+            selector,
+            q_store,
+            store_action_count,
+            step_size,
+            discount,
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_store.generate_id(state, Some(action));
+        self.q_store.get_float(&id)
+    }
+
+    /// Expected action value of the current state of the environment under the selector
+    fn expected_q_estimate(&self) -> f64 {
+        let state = self.environment.get_state();
+        self.selector
+            .get_action_probabilities(&self.environment, &self.q_store, &self.store_action_count)
+            .into_iter()
+            .map(|(action, probability)| probability * self.get_q_estimate(state.clone(), action))
+            .sum()
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentExpectedSarsa<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        self.selector.select_action(&mut self.environment, &self.q_store, &self.store_action_count)
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Bootstraps from the expected action value of the next state under the selector's
+    /// probabilities, so no next action has to be sampled
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        let target = if is_terminal {
+            reward
+        } else {
+            reward + self.discount * self.expected_q_estimate()
+        };
+        let id = self.q_store.generate_id(state, Some(action));
+        let current_q_estimate = self.q_store.get_float(&id);
+        let new_q_estimate = current_q_estimate + self.step_size * (target - current_q_estimate);
+        self.q_store.store_float(id, new_q_estimate);
+    }
+
+    fn reset(&mut self) {
+        self.environment.reset();
+    }
+}
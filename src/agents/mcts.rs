@@ -1,11 +1,52 @@
-use crate::{ environment::Environment, store::Store };
+use std::{ collections::HashSet, io, path::Path };
 
-use super::selector::Selector;
+use rand::{ seq::SliceRandom, Rng };
+use serde::{ de::DeserializeOwned, Serialize };
+
+use crate::{
+    environment::{ Environment, ResetToState },
+    store::{ store_path, StateActionStore, StoreFormat },
+};
+
+use super::{ agent::Agent, selector::Selector };
+
+/// Which occurrences of a state action pair in an episode are credited with the return
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VisitMode {
+    /// only the first occurrence in each episode
+    FirstVisit,
+    /// every occurrence
+    EveryVisit,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MctsConfig {
+    pub visit_mode: VisitMode,
+    /// discount factor (gamma) of future rewards
+    pub discount: f64,
+    /// number of steps after which an episode is truncated, needed for environments
+    /// that never terminate
+    pub max_episode_length: Option<usize>,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            visit_mode: VisitMode::EveryVisit,
+            discount: 1.0,
+            max_episode_length: None,
+        }
+    }
+}
 
 // TODO: add store visit count
-// TODO: CHANGE TO STRUCT ARG
 // TODO: ADD EPSILON DECAY
-pub struct AgentMcts<T: Environment, U: Selector, S: Store> {
+/// Monte Carlo control. Estimates are updated with the returns of each finished episode.
+///
+/// Episodes that are truncated by `max_episode_length` are not terminal: the rewards that
+/// would have followed are unknown, so the returns of their steps bootstrap from the current
+/// estimates of the state the episode was cut off in.
+pub struct AgentMcts<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> {
     environment: T,
     selector: U,
     q_store: S,
@@ -13,11 +54,14 @@ pub struct AgentMcts<T: Environment, U: Selector, S: Store> {
     store_action_count: S,
     store_state_count: S,
     total_actions_taken: usize,
+    episode: Vec<StateActionValue<T::State, T::Action>>,
+    episode_length: usize,
+    config: MctsConfig,
 }
 
-type StateActionValue = (String, usize, f64);
+type StateActionValue<S, A> = (S, A, f64);
 
-impl<T: Environment, U: Selector, S: Store> AgentMcts<T, U, S> {
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentMcts<T, U, S> {
     pub fn new(
         environment: T,
         selector: U,
@@ -25,19 +69,42 @@ impl<T: Environment, U: Selector, S: Store> AgentMcts<T, U, S> {
         state_value_store: S,
         store_action_count: S,
         store_state_count: S
+    ) -> AgentMcts<T, U, S> {
+        AgentMcts::with_config(
+            environment,
+            selector,
+            q_store,
+            state_value_store,
+            store_action_count,
+            store_state_count,
+            MctsConfig::default()
+        )
+    }
+
+    pub fn with_config(
+        environment: T,
+        selector: U,
+        q_store: S,
+        state_value_store: S,
+        store_action_count: S,
+        store_state_count: S,
+        config: MctsConfig
     ) -> AgentMcts<T, U, S> {
         AgentMcts {
             environment,
             selector,
-            q_store: q_store,
-            state_value_store: state_value_store,
+            q_store,
+            state_value_store,
             store_action_count,
             store_state_count,
             total_actions_taken: 0,
+            episode: Vec::new(),
+            episode_length: 0,
+            config,
         }
     }
 
-    pub fn select_action(&mut self) -> usize {
+    pub fn select_action(&mut self) -> T::Action {
         self.selector.select_action(&mut self.environment, &self.q_store, &self.store_action_count)
     }
 
@@ -45,34 +112,26 @@ impl<T: Environment, U: Selector, S: Store> AgentMcts<T, U, S> {
         self.environment.get_number_of_possible_states()
     }
 
-    pub fn take_action(&mut self, action: usize) -> f64 {
-        // record action taken
-        let current_state = self.environment.get_state();
-        let id = self.store_action_count.generate_id(current_state.clone(), Some(action));
-        let current_count = self.store_action_count.get_float(&id);
-        self.store_action_count.store_float(id, current_count + 1.0);
-        // record visit to state
-        let state_id = self.store_state_count.generate_id(current_state, None);
-        let current_state_count = self.store_state_count.get_float(&state_id);
-        self.store_state_count.store_float(state_id, current_state_count + 1.0);
-        // take step
+    /// Visits are only counted once the episode ends, for the occurrences credited with a return
+    pub fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
         self.environment.step(action)
     }
 
-    fn update_q_estimate(&mut self, state: String, action: usize, reward: f64) {
+    fn update_q_estimate(&mut self, state: T::State, action: T::Action, reward: f64) {
         let new_estimate = self.selector.get_new_q_estimate(
             &mut self.environment,
-            &mut self.q_store,
-            &mut self.store_action_count,
+            &self.q_store,
+            &self.store_action_count,
             state.clone(),
-            action,
+            action.clone(),
             reward
         );
-        let id: String = self.q_store.generate_id(state, Some(action));
+        let id = self.q_store.generate_id(state, Some(action));
         self.q_store.store_float(id, new_estimate);
     }
 
-    fn update_state_value_estimate(&mut self, state: String, reward: f64) {
+    fn update_state_value_estimate(&mut self, state: T::State, reward: f64) {
         let new_estimate = self.selector.get_new_value_estimate(
             &mut self.environment,
             &self.state_value_store,
@@ -80,51 +139,222 @@ impl<T: Environment, U: Selector, S: Store> AgentMcts<T, U, S> {
             state.clone(),
             reward
         );
-        let id: String = self.state_value_store.generate_id(state, None);
+        let id = self.state_value_store.generate_id(state, None);
         self.state_value_store.store_float(id, new_estimate);
     }
 
-    pub fn get_state_value_estimate(&self, state: String) -> f64 {
-        let id: String = self.state_value_store.generate_id(state, None);
+    pub fn get_state_value_estimate(&self, state: T::State) -> f64 {
+        let id = self.state_value_store.generate_id(state, None);
         self.state_value_store.get_float(&id)
     }
 
-    pub fn get_state_visit_count(&self, state: String) -> f64 {
-        let id: String = self.store_state_count.generate_id(state, None);
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_store.generate_id(state, Some(action));
+        self.q_store.get_float(&id)
+    }
+
+    pub fn get_state_visit_count(&self, state: T::State) -> f64 {
+        let id = self.store_state_count.generate_id(state, None);
         self.store_state_count.get_float(&id)
     }
 
-    pub fn all_possible_states(&self) -> Vec<String> {
+    pub fn all_possible_states(&self) -> Vec<T::State> {
         self.environment.all_possible_states()
     }
 
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+
+    ///
+    /// Save the q, state value, action count and state count stores to a directory
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - &Path - checkpoint directory, created if missing
+    /// * `format` - StoreFormat - format to write each store in
+    pub fn save_checkpoint(&self, directory: &Path, format: StoreFormat) -> io::Result<()>
+        where T::State: Serialize, T::Action: Serialize
+    {
+        std::fs::create_dir_all(directory)?;
+        self.q_store.save(&store_path(directory, "q", format), format)?;
+        self.state_value_store.save(&store_path(directory, "state_value", format), format)?;
+        self.store_action_count.save(&store_path(directory, "action_count", format), format)?;
+        self.store_state_count.save(&store_path(directory, "state_count", format), format)
+    }
+
+    ///
+    /// Replace the stores of the agent with ones saved by `save_checkpoint`
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - &Path - checkpoint directory
+    /// * `format` - StoreFormat - format the stores were written in
+    pub fn load_checkpoint(&mut self, directory: &Path, format: StoreFormat) -> io::Result<()>
+        where T::State: DeserializeOwned, T::Action: DeserializeOwned
+    {
+        self.q_store = S::load(&store_path(directory, "q", format), format)?;
+        self.state_value_store = S::load(&store_path(directory, "state_value", format), format)?;
+        self.store_action_count = S::load(&store_path(directory, "action_count", format), format)?;
+        self.store_state_count = S::load(&store_path(directory, "state_count", format), format)?;
+        Ok(())
+    }
+
     /// Run an episode of the environment and update q/value estimates
-    /// Returns a vector of state action values
+    /// Returns the total reward of the episode
     pub fn run_episode(&mut self) -> f64 {
-        let mut state_action_values: Vec<StateActionValue> = Vec::new();
-        let mut reward: f64;
+        let mut total_reward = 0.0;
         loop {
             let state = self.environment.get_state();
             let action = self.select_action();
-            reward = self.take_action(action);
-            state_action_values.push((state, action, reward));
-            self.total_actions_taken += 1;
-            if self.environment.is_terminal() {
+            let reward = self.take_action(action.clone());
+            total_reward += reward;
+            let is_terminal = self.environment.is_terminal();
+            self.update_estimate(state, action, reward, is_terminal);
+            if is_terminal || self.is_truncated() {
                 break;
             }
         }
-        // now update q estimates for each state action pair
-        // rewards should be summed from time t to end of episode
+        // reset the environment
+        Agent::reset(self);
+        total_reward
+    }
+
+    /// Update q/value estimates for every step of the finished episode
+    fn update_episode_estimates(&mut self) {
+        let episode = std::mem::take(&mut self.episode);
+        let (first_action_visits, first_state_visits) = match self.config.visit_mode {
+            VisitMode::EveryVisit => (vec![true; episode.len()], vec![true; episode.len()]),
+            VisitMode::FirstVisit => {
+                let mut seen_actions = HashSet::new();
+                let mut seen_states = HashSet::new();
+                episode
+                    .iter()
+                    .map(|(state, action, _)| {
+                        (
+                            seen_actions.insert((state.clone(), action.clone())),
+                            seen_states.insert(state.clone()),
+                        )
+                    })
+                    .unzip()
+            }
+        };
+        // discounted rewards should be summed from time t to end of episode, which for a
+        // truncated episode is estimated by the best q and the value of the state it stopped in
+        let (mut total_reward, mut state_return) = if self.is_truncated() {
+            let last_state = self.environment.get_state();
+            let best_q_estimate = self.environment
+                .get_actions()
+                .into_iter()
+                .map(|action| self.get_q_estimate(last_state.clone(), action))
+                .fold(f64::NEG_INFINITY, f64::max);
+            (best_q_estimate, self.get_state_value_estimate(last_state))
+        } else {
+            (0.0, 0.0)
+        };
+        let steps = episode.into_iter().zip(first_action_visits).zip(first_state_visits);
+        for (((state, action, reward), first_action_visit), first_state_visit) in steps.rev() {
+            total_reward = self.config.discount * total_reward + reward;
+            state_return = self.config.discount * state_return + reward;
+            // every credited return is counted right before it is averaged in, so estimates
+            // stay exact averages of the credited returns. The q update expects the count to
+            // include the return, the state value update adds it itself
+            if first_action_visit {
+                let id = self.store_action_count.generate_id(state.clone(), Some(action.clone()));
+                self.store_action_count.increment_float(id, 1.0);
+                self.update_q_estimate(state.clone(), action, total_reward);
+            }
+            if first_state_visit {
+                self.update_state_value_estimate(state.clone(), state_return);
+                let id = self.store_state_count.generate_id(state, None);
+                self.store_state_count.increment_float(id, 1.0);
+            }
+        }
+    }
+}
+
+impl<T: ResetToState, U: Selector, S: StateActionStore<T::State, T::Action>> AgentMcts<T, U, S> {
+    ///
+    /// Run an episode from a uniformly random start state and first action (exploring starts),
+    /// following the selector afterwards. Returns the total reward of the episode
+    ///
+    /// # Arguments
+    ///
+    /// * `rng` - &mut R - source of the start state and first action
+    pub fn run_episode_with_exploring_start<R: Rng + ?Sized>(&mut self, rng: &mut R) -> f64 {
+        let start_state = self.environment
+            .all_possible_states()
+            .choose(rng)
+            .expect("environment has no states")
+            .clone();
+        self.environment.reset_to_state(&start_state);
+        let mut action = self.environment
+            .get_actions()
+            .choose(rng)
+            .expect("start state has no actions")
+            .clone();
         let mut total_reward = 0.0;
-        for (state, action, reward) in state_action_values.iter().rev() {
-            println!("State: {}, Action: {}, Reward: {}", state, action, reward);
+        loop {
+            let state = self.environment.get_state();
+            let reward = self.take_action(action.clone());
             total_reward += reward;
-            self.update_q_estimate(state.clone(), *action, total_reward);
-            self.update_state_value_estimate(state.clone(), total_reward);
+            let is_terminal = self.environment.is_terminal();
+            self.update_estimate(state, action, reward, is_terminal);
+            if is_terminal || self.is_truncated() {
+                break;
+            }
+            action = self.select_action();
         }
-        // reset the environment
-        self.environment.reset();
-        // return the total reward
+        Agent::reset(self);
         total_reward
     }
 }
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentMcts<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        AgentMcts::select_action(self)
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        AgentMcts::take_action(self, action)
+    }
+
+    /// Estimates are only updated once the episode terminates or is truncated
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        self.episode.push((state, action, reward));
+        self.episode_length += 1;
+        if is_terminal || self.is_truncated() {
+            self.update_episode_estimates();
+        }
+    }
+
+    fn is_truncated(&self) -> bool {
+        match self.config.max_episode_length {
+            Some(max_episode_length) => {
+                self.episode_length >= max_episode_length && !self.environment.is_terminal()
+            }
+            None => false,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.episode.clear();
+        self.episode_length = 0;
+        self.environment.reset();
+    }
+}
@@ -1,4 +1,16 @@
 pub mod agent;
+pub mod double_q_learning;
+pub mod dyna_q;
+pub mod expected_sarsa;
+pub mod n_step_sarsa;
+pub mod n_step_td;
+pub mod off_policy_mc;
+pub mod prioritized_sweeping;
 pub mod q;
+pub mod q_learning;
+pub mod sarsa;
+pub mod sarsa_lambda;
 pub mod selector;
+pub mod td_lambda;
+pub mod uct;
 pub mod mcts;
@@ -0,0 +1,159 @@
+use std::collections::VecDeque;
+
+use crate::{ environment::Environment, store::StateActionStore };
+
+use super::{ agent::{ take_counted_action, Agent }, n_step_td::discounted_return, selector::Selector };
+
+/// n-step SARSA (on-policy n-step TD control).
+///
+/// The action value of a state action pair is moved towards the discounted sum of the next
+/// `n` rewards plus the discounted value of the state action pair reached after them,
+/// `G = r1 + ... + γ^(n-1) rn + γ^n Q(sn, an)`. As with SARSA, `an` is picked by the
+/// selector as soon as `sn` is reached and is the next action returned by `select_action`.
+/// Pairs within `n` steps of the end of an episode are updated with their full return, so an
+/// `n` of `usize::MAX` never bootstraps and gives constant-α Monte Carlo control.
+pub struct AgentNStepSarsa<
+    T: Environment,
+    U: Selector,
+    S: StateActionStore<T::State, T::Action>
+> {
+    environment: T,
+    selector: U,
+    q_store: S,
+    store_action_count: S,
+    n: usize,
+    step_size: f64,
+    discount: f64,
+    /// state action pairs whose value is still waiting for `n` rewards
+    pending: VecDeque<(T::State, T::Action, f64)>,
+    next_action: Option<T::Action>,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentNStepSarsa<
+    T,
+    U,
+    S
+> {
+    ///
+    /// Create a new n-step SARSA agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - policy that is followed and learned about
+    /// * `q_store` - S - store for action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `n` - usize - number of rewards before bootstrapping, at least 1
+    /// * `step_size` - f64 - step size (alpha) of the update
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    pub fn new(
+        environment: T,
+        selector: U,
+        q_store: S,
+        store_action_count: S,
+        n: usize,
+        step_size: f64,
+        discount: f64
+    ) -> AgentNStepSarsa<T, U, S> {
+        assert!(n > 0, "n must be at least 1");
+        AgentNStepSarsa {
+            environment,
+            selector,
+            q_store,
+            store_action_count,
+            n,
+            step_size,
+            discount,
+            pending: VecDeque::new(),
+            next_action: None,
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_store.generate_id(state, Some(action));
+        self.q_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+
+    /// Update the oldest pending pair with its n-step return, bootstrapping from `bootstrap`
+    fn update_oldest_pair(&mut self, bootstrap: f64) {
+        let total_return = discounted_return(
+            self.pending.iter().map(|(_, _, reward)| *reward),
+            bootstrap,
+            self.discount
+        );
+        let (state, action, _) = self.pending.pop_front().expect("no pending state action pair");
+        let id = self.q_store.generate_id(state, Some(action));
+        let current_q_estimate = self.q_store.get_float(&id);
+        let new_q_estimate = current_q_estimate + self.step_size * (total_return - current_q_estimate);
+        self.q_store.store_float(id, new_q_estimate);
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentNStepSarsa<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        match self.next_action.take() {
+            Some(action) => action,
+            None =>
+                self.selector.select_action(
+                    &mut self.environment,
+                    &self.q_store,
+                    &self.store_action_count
+                ),
+        }
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Picks the next action here, as it is bootstrapped from, and updates the pair taken `n`
+    /// steps ago or every pair still waiting when the episode ends
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        self.pending.push_back((state, action, reward));
+        if is_terminal {
+            // nothing follows the end of the episode, so every pending pair is complete
+            while !self.pending.is_empty() {
+                self.update_oldest_pair(0.0);
+            }
+            return;
+        }
+        let next_action = self.selector.select_action(
+            &mut self.environment,
+            &self.q_store,
+            &self.store_action_count
+        );
+        if self.pending.len() == self.n {
+            let bootstrap = self.get_q_estimate(self.environment.get_state(), next_action.clone());
+            self.update_oldest_pair(bootstrap);
+        }
+        self.next_action = Some(next_action);
+    }
+
+    fn reset(&mut self) {
+        self.pending.clear();
+        self.next_action = None;
+        self.environment.reset();
+    }
+}
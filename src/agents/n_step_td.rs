@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+
+use crate::{ environment::Environment, store::StateActionStore };
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+/// n-step TD prediction of state values.
+///
+/// The value of a state is moved towards the discounted sum of the next `n` rewards plus the
+/// discounted value of the state reached after them, `G = r1 + ... + γ^(n-1) rn + γ^n V(sn)`.
+/// States within `n` steps of the end of an episode are updated with their full return, so an
+/// `n` of `usize::MAX` never bootstraps and gives constant-α Monte Carlo.
+///
+/// There are no action values to act on, so the selector is handed the state value store
+/// instead. Only a policy that ignores it makes sense here, such as ε-greedy with ε = 1.
+pub struct AgentNStepTd<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> {
+    environment: T,
+    selector: U,
+    state_value_store: S,
+    store_action_count: S,
+    n: usize,
+    step_size: f64,
+    discount: f64,
+    /// states whose value is still waiting for `n` rewards, along with the reward that followed each
+    pending: VecDeque<(T::State, f64)>,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentNStepTd<T, U, S> {
+    ///
+    /// Create a new n-step TD agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - policy whose state values are estimated
+    /// * `state_value_store` - S - store for state value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `n` - usize - number of rewards before bootstrapping, at least 1
+    /// * `step_size` - f64 - step size (alpha) of the update
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    pub fn new(
+        environment: T,
+        selector: U,
+        state_value_store: S,
+        store_action_count: S,
+        n: usize,
+        step_size: f64,
+        discount: f64
+    ) -> AgentNStepTd<T, U, S> {
+        assert!(n > 0, "n must be at least 1");
+        AgentNStepTd {
+            environment,
+            selector,
+            state_value_store,
+            store_action_count,
+            n,
+            step_size,
+            discount,
+            pending: VecDeque::new(),
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_state_value_estimate(&self, state: T::State) -> f64 {
+        let id = self.state_value_store.generate_id(state, None);
+        self.state_value_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+
+    /// Update the oldest pending state with its n-step return, bootstrapping from `bootstrap`
+    fn update_oldest_state(&mut self, bootstrap: f64) {
+        let total_return = discounted_return(
+            self.pending.iter().map(|(_, reward)| *reward),
+            bootstrap,
+            self.discount
+        );
+        let (state, _) = self.pending.pop_front().expect("no pending state");
+        let id = self.state_value_store.generate_id(state, None);
+        let current_value_estimate = self.state_value_store.get_float(&id);
+        let new_value_estimate =
+            current_value_estimate + self.step_size * (total_return - current_value_estimate);
+        self.state_value_store.store_float(id, new_value_estimate);
+    }
+}
+
+///
+/// Discounted sum of rewards followed by a bootstrapped value
+///
+/// # Arguments
+///
+/// * `rewards` - I - rewards in the order they were received
+/// * `bootstrap` - f64 - estimated value after the last reward, 0 at the end of an episode
+/// * `discount` - f64 - discount factor (gamma) of future rewards
+pub(crate) fn discounted_return<I: DoubleEndedIterator<Item = f64>>(
+    rewards: I,
+    bootstrap: f64,
+    discount: f64
+) -> f64 {
+    rewards.rev().fold(bootstrap, |total_return, reward| reward + discount * total_return)
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentNStepTd<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        self.selector.select_action(
+            &mut self.environment,
+            &self.state_value_store,
+            &self.store_action_count
+        )
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Updates the state visited `n` steps ago, or every state still waiting when the episode ends
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        _action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        self.pending.push_back((state, reward));
+        if is_terminal {
+            // nothing follows the end of the episode, so every pending state is complete
+            while !self.pending.is_empty() {
+                self.update_oldest_state(0.0);
+            }
+        } else if self.pending.len() == self.n {
+            let bootstrap = self.get_state_value_estimate(self.environment.get_state());
+            self.update_oldest_state(bootstrap);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.pending.clear();
+        self.environment.reset();
+    }
+}
@@ -0,0 +1,337 @@
+use std::hash::Hash;
+
+use crate::{ environment::{ Environment, ResetToState }, store::{ StateActionStore, Store } };
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+/// How returns of the behavior policy are reweighted to estimate values of the target policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportanceSampling {
+    /// average of the scaled returns, unbiased but with possibly unbounded variance
+    Ordinary,
+    /// scaled returns averaged by the sum of their weights, biased but with bounded variance
+    Weighted,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct OffPolicyMcConfig {
+    pub sampling: ImportanceSampling,
+    /// discount factor (gamma) of future rewards
+    pub discount: f64,
+}
+
+impl Default for OffPolicyMcConfig {
+    fn default() -> Self {
+        OffPolicyMcConfig {
+            sampling: ImportanceSampling::Weighted,
+            discount: 1.0,
+        }
+    }
+}
+
+/// Deterministic policy whose values are estimated
+pub enum TargetPolicy<S, A> {
+    /// greedy with respect to the learned action values, for control
+    Greedy,
+    /// fixed mapping from states to actions, for prediction
+    Fixed(Box<dyn Fn(&S) -> A>),
+}
+
+/// One step of an episode generated by the behavior policy
+struct BehaviorStep<S, A> {
+    state: S,
+    action: A,
+    reward: f64,
+    /// probability the behavior policy had of taking the action
+    probability: f64,
+    /// actions that were available in the state
+    actions: Vec<A>,
+}
+
+/// Off-policy Monte Carlo prediction and control.
+///
+/// Episodes are generated by the behavior selector and their returns are reweighted by the
+/// importance sampling ratio `π(a|s) / b(a|s)` of every following step, so the stored
+/// estimates belong to the target policy. Estimates are updated once the episode terminates.
+pub struct AgentOffPolicyMc<
+    T: Environment,
+    U: Selector,
+    S: StateActionStore<T::State, T::Action>
+> {
+    environment: T,
+    behavior: U,
+    target: TargetPolicy<T::State, T::Action>,
+    q_store: S,
+    state_value_store: S,
+    /// visit counts for ordinary and cumulative weights for weighted importance sampling
+    q_weight_store: S,
+    state_weight_store: S,
+    store_action_count: S,
+    config: OffPolicyMcConfig,
+    episode: Vec<BehaviorStep<T::State, T::Action>>,
+    /// probability and available actions of the last selected action
+    behavior_probability: f64,
+    behavior_actions: Vec<T::Action>,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentOffPolicyMc<
+    T,
+    U,
+    S
+> {
+    ///
+    /// Create a new off-policy Monte Carlo agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `behavior` - U - selector that generates the episodes, it must give every action of the target policy a non-zero probability
+    /// * `target` - TargetPolicy - policy whose values are estimated
+    /// * `q_store` - S - store for action value estimates
+    /// * `state_value_store` - S - store for state value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `config` - OffPolicyMcConfig - importance sampling method and discount
+    pub fn new(
+        environment: T,
+        behavior: U,
+        target: TargetPolicy<T::State, T::Action>,
+        q_store: S,
+        state_value_store: S,
+        store_action_count: S,
+        config: OffPolicyMcConfig
+    ) -> AgentOffPolicyMc<T, U, S> {
+        AgentOffPolicyMc {
+            environment,
+            behavior,
+            target,
+            q_store,
+            state_value_store,
+            q_weight_store: S::new(),
+            state_weight_store: S::new(),
+            store_action_count,
+            config,
+            episode: Vec::new(),
+            behavior_probability: 1.0,
+            behavior_actions: Vec::new(),
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_store.generate_id(state, Some(action));
+        self.q_store.get_float(&id)
+    }
+
+    pub fn get_state_value_estimate(&self, state: T::State) -> f64 {
+        let id = self.state_value_store.generate_id(state, None);
+        self.state_value_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+
+    ///
+    /// Action the target policy takes in a state
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - &T::State - state to act in
+    /// * `actions` - &[T::Action] - actions available in the state
+    pub fn get_target_action(&self, state: &T::State, actions: &[T::Action]) -> T::Action {
+        match &self.target {
+            TargetPolicy::Fixed(policy) => policy(state),
+            TargetPolicy::Greedy => {
+                // ties go to the last action, as with the greedy selectors
+                let mut max = f64::NEG_INFINITY;
+                let mut max_action = None;
+                for action in actions {
+                    let q_estimate = self.get_q_estimate(state.clone(), action.clone());
+                    if q_estimate >= max {
+                        max = q_estimate;
+                        max_action = Some(action);
+                    }
+                }
+                max_action.expect("state has no actions").clone()
+            }
+        }
+    }
+
+    /// Run an episode of the behavior policy and update the estimates of the target policy
+    /// Returns the total reward of the episode
+    pub fn run_episode(&mut self) -> f64 {
+        let mut total_reward = 0.0;
+        loop {
+            let state = self.environment.get_state();
+            let action = Agent::select_action(self);
+            let reward = Agent::take_action(self, action.clone());
+            total_reward += reward;
+            let is_terminal = self.environment.is_terminal();
+            self.update_estimate(state, action, reward, is_terminal);
+            if is_terminal {
+                break;
+            }
+        }
+        self.environment.reset();
+        total_reward
+    }
+
+    /// Update estimates for every step of the finished episode, walking backwards so the
+    /// importance sampling ratio of the remaining steps can be accumulated
+    fn update_episode_estimates(&mut self) {
+        let episode = std::mem::take(&mut self.episode);
+        let mut total_return = 0.0;
+        // importance sampling ratio of the steps after the current one
+        let mut weight = 1.0;
+        for step in episode.into_iter().rev() {
+            total_return = self.config.discount * total_return + step.reward;
+            // the action is given for its action value, so only later steps are reweighted
+            let id = self.q_store.generate_id(step.state.clone(), Some(step.action.clone()));
+            update_importance_sampling_estimate(
+                &mut self.q_store,
+                &mut self.q_weight_store,
+                id,
+                weight,
+                total_return,
+                self.config.sampling
+            );
+            // the greedy target is taken after the update, so it reflects the new estimate
+            let target_action = self.get_target_action(&step.state, &step.actions);
+            if target_action != step.action {
+                weight = 0.0;
+            } else {
+                weight /= step.probability;
+            }
+            let id = self.state_value_store.generate_id(step.state, None);
+            update_importance_sampling_estimate(
+                &mut self.state_value_store,
+                &mut self.state_weight_store,
+                id,
+                weight,
+                total_return,
+                self.config.sampling
+            );
+        }
+    }
+}
+
+impl<T: ResetToState, U: Selector, S: StateActionStore<T::State, T::Action>> AgentOffPolicyMc<
+    T,
+    U,
+    S
+> {
+    ///
+    /// Run an episode of the behavior policy from a given start state
+    /// Returns the total reward of the episode
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - &T::State - state the episode starts in
+    pub fn run_episode_from_state(&mut self, state: &T::State) -> f64 {
+        self.environment.reset_to_state(state);
+        self.run_episode()
+    }
+}
+
+///
+/// Move an estimate towards a return scaled by its importance sampling ratio
+///
+/// # Arguments
+///
+/// * `estimate_store` - &mut S - store holding the estimate
+/// * `weight_store` - &mut S - store holding the visit count or the cumulative weight of the estimate
+/// * `id` - K - key of the estimate
+/// * `weight` - f64 - importance sampling ratio of the return
+/// * `total_return` - f64 - return following the state or state action pair
+/// * `sampling` - ImportanceSampling - how the scaled returns are averaged
+fn update_importance_sampling_estimate<K: Hash + Eq + Clone, S: Store<K>>(
+    estimate_store: &mut S,
+    weight_store: &mut S,
+    id: K,
+    weight: f64,
+    total_return: f64,
+    sampling: ImportanceSampling
+) {
+    let estimate = estimate_store.get_float(&id);
+    let new_estimate = match sampling {
+        ImportanceSampling::Ordinary => {
+            // every return counts towards the average, including the ones scaled to zero
+            weight_store.increment_float(id.clone(), 1.0);
+            let num_visits = weight_store.get_float(&id);
+            estimate + (weight * total_return - estimate) / num_visits
+        }
+        ImportanceSampling::Weighted => {
+            if weight == 0.0 {
+                return;
+            }
+            weight_store.increment_float(id.clone(), weight);
+            let cumulative_weight = weight_store.get_float(&id);
+            estimate + (weight / cumulative_weight) * (total_return - estimate)
+        }
+    };
+    estimate_store.store_float(id, new_estimate);
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentOffPolicyMc<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    /// Select an action with the behavior policy and remember its probability
+    fn select_action(&mut self) -> T::Action {
+        self.behavior_actions = self.environment.get_actions();
+        let action = self.behavior.select_action(
+            &mut self.environment,
+            &self.q_store,
+            &self.store_action_count
+        );
+        self.behavior_probability = self.behavior
+            .get_action_probabilities(&self.environment, &self.q_store, &self.store_action_count)
+            .into_iter()
+            .find(|(candidate, _)| *candidate == action)
+            .map(|(_, probability)| probability)
+            .unwrap_or(0.0);
+        // the importance sampling ratio divides by this probability
+        assert!(
+            self.behavior_probability > 0.0,
+            "the behavior policy must give the action it selected a non-zero probability"
+        );
+        action
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Estimates are only updated once the episode terminates
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        self.episode.push(BehaviorStep {
+            state,
+            action,
+            reward,
+            probability: self.behavior_probability,
+            actions: std::mem::take(&mut self.behavior_actions),
+        });
+        if is_terminal {
+            self.update_episode_estimates();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.episode.clear();
+        self.environment.reset();
+    }
+}
@@ -0,0 +1,294 @@
+use std::{ cmp::Ordering, collections::BinaryHeap };
+
+use rustc_hash::{ FxHashMap, FxHashSet };
+
+use crate::{ environment::Environment, store::StateActionStore };
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+#[derive(Debug, Clone, Copy)]
+pub struct PrioritizedSweepingConfig {
+    /// largest number of simulated updates from the model after every real step
+    pub planning_steps: usize,
+    /// step size (alpha) of the update
+    pub step_size: f64,
+    /// discount factor (gamma) of future rewards
+    pub discount: f64,
+    /// state action pairs whose TD error is no larger than this are not queued
+    pub threshold: f64,
+}
+
+impl Default for PrioritizedSweepingConfig {
+    fn default() -> Self {
+        PrioritizedSweepingConfig {
+            planning_steps: 5,
+            step_size: 0.1,
+            discount: 0.95,
+            threshold: 0.0001,
+        }
+    }
+}
+
+/// State action pair waiting in the queue, ordered by priority
+struct Queued<S, A> {
+    priority: f64,
+    state: S,
+    action: A,
+}
+
+impl<S, A> PartialEq for Queued<S, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority.total_cmp(&other.priority) == Ordering::Equal
+    }
+}
+
+impl<S, A> Eq for Queued<S, A> {}
+
+impl<S, A> PartialOrd for Queued<S, A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, A> Ord for Queued<S, A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority.total_cmp(&other.priority)
+    }
+}
+
+/// Outcome last observed for a state action pair
+struct ModelEntry<S> {
+    reward: f64,
+    next_state: S,
+    is_terminal: bool,
+}
+
+type Model<S, A> = FxHashMap<(S, A), ModelEntry<S>>;
+
+type Predecessors<S, A> = FxHashMap<S, FxHashSet<(S, A)>>;
+
+/// Tabular prioritized sweeping.
+///
+/// Like Dyna-Q, real experience is recorded in a deterministic model `(s, a) -> (r, s')`,
+/// but instead of replaying pairs uniformly, pairs are queued by the magnitude of their
+/// TD error. After every real step the most urgent pairs are updated, and each update
+/// queues the predecessors of the updated state, so changes propagate backwards from where
+/// they happen. All updates of action values are made while planning.
+pub struct AgentPrioritizedSweeping<
+    T: Environment,
+    U: Selector,
+    S: StateActionStore<T::State, T::Action>
+> {
+    environment: T,
+    selector: U,
+    q_store: S,
+    store_action_count: S,
+    config: PrioritizedSweepingConfig,
+    model: Model<T::State, T::Action>,
+    /// state action pairs observed to lead to each state
+    predecessors: Predecessors<T::State, T::Action>,
+    /// actions available in every state seen so far
+    state_actions: FxHashMap<T::State, Vec<T::Action>>,
+    queue: BinaryHeap<Queued<T::State, T::Action>>,
+    /// priority each queued pair currently holds, older queue entries are skipped when popped
+    queued_priorities: FxHashMap<(T::State, T::Action), f64>,
+    total_actions_taken: usize,
+    total_updates: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentPrioritizedSweeping<
+    T,
+    U,
+    S
+> {
+    ///
+    /// Create a new prioritized sweeping agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - behavior policy
+    /// * `q_store` - S - store for action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `config` - PrioritizedSweepingConfig - planning steps, step size, discount and threshold
+    pub fn new(
+        environment: T,
+        selector: U,
+        q_store: S,
+        store_action_count: S,
+        config: PrioritizedSweepingConfig
+    ) -> AgentPrioritizedSweeping<T, U, S> {
+        AgentPrioritizedSweeping {
+            environment,
+            selector,
+            q_store,
+            store_action_count,
+            config,
+            model: FxHashMap::default(),
+            predecessors: FxHashMap::default(),
+            state_actions: FxHashMap::default(),
+            queue: BinaryHeap::new(),
+            queued_priorities: FxHashMap::default(),
+            total_actions_taken: 0,
+            total_updates: 0,
+        }
+    }
+
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_store.generate_id(state, Some(action));
+        self.q_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+
+    /// Number of action value updates made so far
+    pub fn get_total_updates(&self) -> usize {
+        self.total_updates
+    }
+
+    /// Largest action value estimate of a state seen before, 0 for unseen states
+    fn max_q_estimate(&self, state: &T::State) -> f64 {
+        match self.state_actions.get(state) {
+            Some(actions) =>
+                actions
+                    .iter()
+                    .map(|action| self.get_q_estimate(state.clone(), action.clone()))
+                    .fold(f64::NEG_INFINITY, f64::max),
+            None => 0.0,
+        }
+    }
+
+    /// Remember the actions of the current state of the environment the first time it is seen
+    fn register_current_state(&mut self) {
+        let state = self.environment.get_state();
+        if !self.state_actions.contains_key(&state) {
+            let actions = self.environment.get_actions();
+            self.state_actions.insert(state, actions);
+        }
+    }
+
+    /// Target of the one-step update of a modelled state action pair
+    fn target(&self, state: &T::State, action: &T::Action) -> f64 {
+        let entry = &self.model[&(state.clone(), action.clone())];
+        // terminal states have no future value to bootstrap from
+        if entry.is_terminal {
+            entry.reward
+        } else {
+            entry.reward + self.config.discount * self.max_q_estimate(&entry.next_state)
+        }
+    }
+
+    /// Queue a modelled pair if its TD error is large enough, keeping the higher priority
+    /// if it is already queued
+    fn queue_if_urgent(&mut self, state: T::State, action: T::Action) {
+        let td_error = self.target(&state, &action) - self.get_q_estimate(state.clone(), action.clone());
+        let priority = td_error.abs();
+        if priority <= self.config.threshold {
+            return;
+        }
+        let key = (state, action);
+        if let Some(queued_priority) = self.queued_priorities.get(&key) {
+            if *queued_priority >= priority {
+                return;
+            }
+        }
+        self.queued_priorities.insert(key.clone(), priority);
+        let (state, action) = key;
+        self.queue.push(Queued {
+            priority,
+            state,
+            action,
+        });
+    }
+
+    /// Pop the most urgent pair, skipping queue entries that were superseded
+    fn pop_most_urgent(&mut self) -> Option<(T::State, T::Action)> {
+        while let Some(queued) = self.queue.pop() {
+            let key = (queued.state, queued.action);
+            if self.queued_priorities.get(&key) == Some(&queued.priority) {
+                self.queued_priorities.remove(&key);
+                return Some(key);
+            }
+        }
+        None
+    }
+
+    /// Update the most urgent pairs and queue the predecessors of the states they update
+    fn plan(&mut self) {
+        for _ in 0..self.config.planning_steps {
+            let (state, action) = match self.pop_most_urgent() {
+                Some(key) => key,
+                None => {
+                    break;
+                }
+            };
+            let target = self.target(&state, &action);
+            let id = self.q_store.generate_id(state.clone(), Some(action));
+            let current_q_estimate = self.q_store.get_float(&id);
+            let new_q_estimate =
+                current_q_estimate + self.config.step_size * (target - current_q_estimate);
+            self.q_store.store_float(id, new_q_estimate);
+            self.total_updates += 1;
+            let predecessors: Vec<(T::State, T::Action)> = match self.predecessors.get(&state) {
+                Some(predecessors) => predecessors.iter().cloned().collect(),
+                None => vec![],
+            };
+            for (predecessor_state, predecessor_action) in predecessors {
+                self.queue_if_urgent(predecessor_state, predecessor_action);
+            }
+        }
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentPrioritizedSweeping<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        self.selector.select_action(&mut self.environment, &self.q_store, &self.store_action_count)
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.register_current_state();
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Records the step in the model, queues its pair if the change would be large enough
+    /// and then sweeps the queue
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        if !is_terminal {
+            self.register_current_state();
+        }
+        let next_state = self.environment.get_state();
+        self.predecessors
+            .entry(next_state.clone())
+            .or_default()
+            .insert((state.clone(), action.clone()));
+        self.model.insert((state.clone(), action.clone()), ModelEntry {
+            reward,
+            next_state,
+            is_terminal,
+        });
+        self.queue_if_urgent(state, action);
+        self.plan();
+    }
+
+    fn reset(&mut self) {
+        self.environment.reset();
+    }
+}
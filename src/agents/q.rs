@@ -1,8 +1,18 @@
-use crate::{ environment::Environment, store::Store };
+use std::{ io, path::Path };
 
-use super::{ agent::Agent, selector::Selector };
+use serde::{ de::DeserializeOwned, Serialize };
 
-pub struct AgentQ<T: Environment, U: Selector, S: Store> {
+use crate::{
+    environment::Environment,
+    store::{ store_path, StateActionStore, StoreFormat },
+};
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+/// Sample average action value learner for bandit problems. Estimates are averages of the
+/// rewards observed for each state action pair, without bootstrapping from the next state.
+/// See `AgentQLearning` for TD control.
+pub struct AgentQ<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> {
     environment: T,
     selector: U,
     q_store: S,
@@ -11,7 +21,7 @@ pub struct AgentQ<T: Environment, U: Selector, S: Store> {
     total_actions_taken: usize,
 }
 
-impl<T: Environment, U: Selector, S: Store> AgentQ<T, U, S> {
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentQ<T, U, S> {
     pub fn new(
         environment: T,
         selector: U,
@@ -22,83 +32,108 @@ impl<T: Environment, U: Selector, S: Store> AgentQ<T, U, S> {
         AgentQ {
             environment,
             selector,
-            q_store: q_store,
-            state_value_store: state_value_store,
+            q_store,
+            state_value_store,
             store_action_count,
             total_actions_taken: 0,
         }
     }
 
-    pub fn select_action(&mut self) -> usize {
+    pub fn select_action(&mut self) -> T::Action {
         self.selector.select_action(&mut self.environment, &self.q_store, &self.store_action_count)
     }
 
-    pub fn take_action(&mut self, action: usize) -> f64 {
-        // record action taken
-        let current_state = self.environment.get_state();
-        let id = self.store_action_count.generate_id(current_state, Some(action));
-        let current_count = self.store_action_count.get_float(&id);
-        self.store_action_count.store_float(id, current_count + 1.0);
-        // take step
-        self.environment.step(action)
+    pub fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
     }
 
-    fn update_q_estimate(&mut self, state: String, action: usize, reward: f64) {
-        let new_estimate = self.selector.get_new_q_estimate(
+    fn update_q_estimate(&mut self, state: T::State, action: T::Action, reward: f64) {
+        self.selector.update_q_estimates(
             &mut self.environment,
             &mut self.q_store,
-            &mut self.store_action_count,
-            state.clone(),
-            action,
-            reward
-        );
-        let id: String = self.q_store.generate_id(state, Some(action));
-        self.q_store.store_float(id, new_estimate);
-    }
-
-    fn update_state_value_estimate(&mut self, state: String, reward: f64) {
-        let new_estimate = self.selector.get_new_value_estimate(
-            &mut self.environment,
-            &self.state_value_store,
             &self.store_action_count,
-            state.clone(),
+            state,
+            action,
             reward
         );
-        let id: String = format!("{}", state);
-        self.state_value_store.store_float(id, new_estimate);
     }
 
-    fn get_q_estimate(&self, state: String, action: usize) -> f64 {
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
         let id = self.q_store.generate_id(state, Some(action));
         self.q_store.get_float(&id)
     }
 
-    fn get_value_estimate(&self, state: String) -> f64 {
+    pub fn get_value_estimate(&self, state: T::State) -> f64 {
         let id = self.state_value_store.generate_id(state, None);
         self.state_value_store.get_float(&id)
     }
 
-    fn get_total_actions_taken(&self) -> usize {
+    pub fn get_total_actions_taken(&self) -> usize {
         self.total_actions_taken
     }
+
+    ///
+    /// Save the q, state value and action count stores to a directory
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - &Path - checkpoint directory, created if missing
+    /// * `format` - StoreFormat - format to write each store in
+    pub fn save_checkpoint(&self, directory: &Path, format: StoreFormat) -> io::Result<()>
+        where T::State: Serialize, T::Action: Serialize
+    {
+        std::fs::create_dir_all(directory)?;
+        self.q_store.save(&store_path(directory, "q", format), format)?;
+        self.state_value_store.save(&store_path(directory, "state_value", format), format)?;
+        self.store_action_count.save(&store_path(directory, "action_count", format), format)
+    }
+
+    ///
+    /// Replace the stores of the agent with ones saved by `save_checkpoint`
+    ///
+    /// # Arguments
+    ///
+    /// * `directory` - &Path - checkpoint directory
+    /// * `format` - StoreFormat - format the stores were written in
+    pub fn load_checkpoint(&mut self, directory: &Path, format: StoreFormat) -> io::Result<()>
+        where T::State: DeserializeOwned, T::Action: DeserializeOwned
+    {
+        self.q_store = S::load(&store_path(directory, "q", format), format)?;
+        self.state_value_store = S::load(&store_path(directory, "state_value", format), format)?;
+        self.store_action_count = S::load(&store_path(directory, "action_count", format), format)?;
+        Ok(())
+    }
 }
 
-impl<T: Environment, U: Selector, S: Store> Agent for AgentQ<T, U, S> {
-    fn select_action(&mut self) -> usize {
-        self.selector.select_action(&mut self.environment, &self.q_store, &self.store_action_count)
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent for AgentQ<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
     }
 
-    fn take_action(&mut self, action: usize) -> f64 {
-        // record action taken
-        let current_state = self.environment.get_state();
-        let id = self.store_action_count.generate_id(current_state, Some(action));
-        let current_count = self.store_action_count.get_float(&id);
-        self.store_action_count.store_float(id, current_count + 1.0);
-        // take step
-        self.environment.step(action)
+    fn select_action(&mut self) -> T::Action {
+        AgentQ::select_action(self)
     }
 
-    fn update_estimate(&mut self, state: String, action: usize, reward: f64, _is_terminal: bool) {
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        AgentQ::take_action(self, action)
+    }
+
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        _is_terminal: bool
+    ) {
         self.update_q_estimate(state, action, reward);
     }
+
+    fn reset(&mut self) {
+        self.environment.reset();
+    }
 }
@@ -0,0 +1,124 @@
+use crate::{ environment::Environment, store::StateActionStore };
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+/// Tabular SARSA (on-policy TD control).
+///
+/// The action value of the state action pair is moved towards `r + discount * Q(s', a')`,
+/// where `a'` is the action the selector picks next. That action is remembered and is the
+/// one returned by the following call to `select_action`.
+pub struct AgentSarsa<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> {
+    environment: T,
+    selector: U,
+    q_store: S,
+    store_action_count: S,
+    step_size: f64,
+    discount: f64,
+    next_action: Option<T::Action>,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentSarsa<T, U, S> {
+    ///
+    /// Create a new SARSA agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - policy that is followed and learned about
+    /// * `q_store` - S - store for action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `step_size` - f64 - step size (alpha) of the update
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    pub fn new(
+        environment: T,
+        selector: U,
+        q_store: S,
+        store_action_count: S,
+        step_size: f64,
+        discount: f64
+    ) -> AgentSarsa<T, U, S> {
+        AgentSarsa {
+            environment,
+            selector,
+            q_store,
+            store_action_count,
+            step_size,
+            discount,
+            next_action: None,
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_store.generate_id(state, Some(action));
+        self.q_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentSarsa<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        match self.next_action.take() {
+            Some(action) => action,
+            None =>
+                self.selector.select_action(
+                    &mut self.environment,
+                    &self.q_store,
+                    &self.store_action_count
+                ),
+        }
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Picks the next action here, as its action value is part of the target, and hands it to
+    /// the next `select_action`
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        let target = if is_terminal {
+            reward
+        } else {
+            let next_action = self.selector.select_action(
+                &mut self.environment,
+                &self.q_store,
+                &self.store_action_count
+            );
+            let next_q_estimate = self.get_q_estimate(
+                self.environment.get_state(),
+                next_action.clone()
+            );
+            self.next_action = Some(next_action);
+            reward + self.discount * next_q_estimate
+        };
+        let id = self.q_store.generate_id(state, Some(action));
+        let current_q_estimate = self.q_store.get_float(&id);
+        let new_q_estimate = current_q_estimate + self.step_size * (target - current_q_estimate);
+        self.q_store.store_float(id, new_q_estimate);
+    }
+
+    fn reset(&mut self) {
+        self.next_action = None;
+        self.environment.reset();
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{ environment::Environment, store::StateActionStore };
+
+use super::{
+    agent::{ take_counted_action, Agent },
+    selector::Selector,
+    td_lambda::{ EligibilityTraces, TdLambdaConfig },
+};
+
+/// SARSA(λ) (on-policy TD control with eligibility traces).
+///
+/// Every step the SARSA error `r + γ Q(s', a') - Q(s, a)` is applied to all state action pairs
+/// in proportion to their trace, which is raised when the pair is taken and decays by `γλ`
+/// each step. As with SARSA, `a'` is picked by the selector as soon as `s'` is reached and is
+/// the next action returned by `select_action`.
+pub struct AgentSarsaLambda<
+    T: Environment,
+    U: Selector,
+    S: StateActionStore<T::State, T::Action>
+> {
+    environment: T,
+    selector: U,
+    q_store: S,
+    store_action_count: S,
+    traces: EligibilityTraces<T::State, T::Action>,
+    config: TdLambdaConfig,
+    next_action: Option<T::Action>,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentSarsaLambda<
+    T,
+    U,
+    S
+> {
+    ///
+    /// Create a new SARSA(λ) agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - policy that is followed and learned about
+    /// * `q_store` - S - store for action value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `config` - TdLambdaConfig - trace type, λ, step size and discount
+    pub fn new(
+        environment: T,
+        selector: U,
+        q_store: S,
+        store_action_count: S,
+        config: TdLambdaConfig
+    ) -> AgentSarsaLambda<T, U, S> {
+        AgentSarsaLambda {
+            environment,
+            selector,
+            q_store,
+            store_action_count,
+            traces: EligibilityTraces::new(),
+            config,
+            next_action: None,
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_q_estimate(&self, state: T::State, action: T::Action) -> f64 {
+        let id = self.q_store.generate_id(state, Some(action));
+        self.q_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentSarsaLambda<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        match self.next_action.take() {
+            Some(action) => action,
+            None =>
+                self.selector.select_action(
+                    &mut self.environment,
+                    &self.q_store,
+                    &self.store_action_count
+                ),
+        }
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Picks the next action here, as in SARSA, and applies the error to every pair with a trace
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        let target = if is_terminal {
+            reward
+        } else {
+            let next_action = self.selector.select_action(
+                &mut self.environment,
+                &self.q_store,
+                &self.store_action_count
+            );
+            let next_q_estimate = self.get_q_estimate(
+                self.environment.get_state(),
+                next_action.clone()
+            );
+            self.next_action = Some(next_action);
+            reward + self.config.discount * next_q_estimate
+        };
+        let td_error = target - self.get_q_estimate(state.clone(), action.clone());
+        self.traces.decay(self.config.discount * self.config.lambda);
+        let id = self.q_store.generate_id(state, Some(action));
+        self.traces.mark_visit(id, self.config.trace_type, self.config.step_size);
+        self.traces.apply(&mut self.q_store, self.config.step_size * td_error);
+        if is_terminal {
+            self.traces.clear();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.traces.clear();
+        self.next_action = None;
+        self.environment.reset();
+    }
+}
@@ -1,27 +1,56 @@
-use crate::{ environment::Environment, store::Store };
+use crate::{ environment::Environment, store::StateActionStore };
 
 pub trait Selector {
-    fn select_action<T: Environment, S: Store>(
-        &self,
+    fn select_action<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &mut self,
         environment: &mut T,
         store: &S,
         store_action_count: &S
-    ) -> usize;
-    fn get_new_q_estimate<T: Environment, S: Store>(
+    ) -> T::Action;
+    /// Probability of selecting each action in the current state of the environment
+    fn get_action_probabilities<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &self,
+        environment: &T,
+        store: &S,
+        store_action_count: &S
+    ) -> Vec<(T::Action, f64)>;
+    fn get_new_q_estimate<T: Environment, S: StateActionStore<T::State, T::Action>>(
         &self,
         environment: &mut T,
         store: &S,
         store_action_count: &S,
-        state: String,
-        action: usize,
+        state: T::State,
+        action: T::Action,
         reward: f64
     ) -> f64;
-    fn get_new_value_estimate<T: Environment, S: Store>(
+    /// Store the new q estimate of the action taken. Selectors whose update also moves the
+    /// estimates of the actions not taken override this.
+    fn update_q_estimates<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &mut self,
+        environment: &mut T,
+        store: &mut S,
+        store_action_count: &S,
+        state: T::State,
+        action: T::Action,
+        reward: f64
+    ) {
+        let new_estimate = self.get_new_q_estimate(
+            environment,
+            store,
+            store_action_count,
+            state.clone(),
+            action.clone(),
+            reward
+        );
+        let id = store.generate_id(state, Some(action));
+        store.store_float(id, new_estimate);
+    }
+    fn get_new_value_estimate<T: Environment, S: StateActionStore<T::State, T::Action>>(
         &self,
         environment: &mut T,
         store: &S,
         store_action_count: &S,
-        state: String,
+        state: T::State,
         reward: f64
     ) -> f64;
 }
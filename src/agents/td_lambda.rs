@@ -0,0 +1,356 @@
+use rustc_hash::FxHashMap;
+
+use crate::{
+    agents::agent::{ Action, State },
+    environment::Environment,
+    store::{ StateActionStore, StoreId },
+};
+
+use super::{ agent::{ take_counted_action, Agent }, selector::Selector };
+
+/// How the eligibility trace of a state or state action pair is raised when it is visited
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceType {
+    /// add 1 to the trace
+    Accumulating,
+    /// set the trace to 1
+    Replacing,
+    /// `e ← γλe + (1 - αγλe)` for the visited state or pair, which moves the trace towards 1
+    /// by `1 - α` instead of adding 1 as accumulating traces do
+    Dutch,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TdLambdaConfig {
+    pub trace_type: TraceType,
+    /// trace decay rate, 0 gives one-step TD and 1 gives Monte Carlo like updates
+    pub lambda: f64,
+    /// step size (alpha) of the update
+    pub step_size: f64,
+    /// discount factor (gamma) of future rewards
+    pub discount: f64,
+}
+
+impl Default for TdLambdaConfig {
+    fn default() -> Self {
+        TdLambdaConfig {
+            trace_type: TraceType::Accumulating,
+            lambda: 0.9,
+            step_size: 0.1,
+            discount: 1.0,
+        }
+    }
+}
+
+/// Eligibility traces of the state or state action pairs visited in the current episode.
+///
+/// Only visited ids are kept, so decaying and applying the traces costs time in the number of
+/// pairs visited since the last `clear` instead of the size of the state space.
+pub(crate) struct EligibilityTraces<St: State, A: Action> {
+    /// index of every visited id in `traces`
+    indices: FxHashMap<StoreId<St, A>, usize>,
+    traces: Vec<(StoreId<St, A>, f64)>,
+}
+
+impl<St: State, A: Action> EligibilityTraces<St, A> {
+    pub(crate) fn new() -> EligibilityTraces<St, A> {
+        EligibilityTraces {
+            indices: FxHashMap::default(),
+            traces: Vec::new(),
+        }
+    }
+
+    /// Multiply every trace by `decay`, which is `discount * lambda`
+    pub(crate) fn decay(&mut self, decay: f64) {
+        for (_, trace) in self.traces.iter_mut() {
+            *trace *= decay;
+        }
+    }
+
+    ///
+    /// Raise the eligibility trace of a visited state or state action pair
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - StoreId - visited state or state action pair
+    /// * `trace_type` - TraceType - how the trace is raised
+    /// * `step_size` - f64 - step size (alpha), used by dutch traces
+    pub(crate) fn mark_visit(&mut self, id: StoreId<St, A>, trace_type: TraceType, step_size: f64) {
+        let next_index = self.traces.len();
+        let index = *self.indices.entry(id.clone()).or_insert(next_index);
+        if index == next_index {
+            self.traces.push((id, 0.0));
+        }
+        let trace = &mut self.traces[index].1;
+        *trace = match trace_type {
+            TraceType::Accumulating => *trace + 1.0,
+            TraceType::Replacing => 1.0,
+            // the trace was already decayed by γλ this step
+            TraceType::Dutch => (1.0 - step_size) * *trace + 1.0,
+        };
+    }
+
+    ///
+    /// Move every estimate with a non-zero trace by `amount` times its trace
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - &mut S - store holding the estimates
+    /// * `amount` - f64 - step size times the TD error
+    pub(crate) fn apply<S: StateActionStore<St, A>>(&self, store: &mut S, amount: f64) {
+        for (id, trace) in self.traces.iter() {
+            if *trace != 0.0 {
+                store.increment_float(id.clone(), amount * trace);
+            }
+        }
+    }
+
+    /// Forget every trace, at the end of an episode
+    pub(crate) fn clear(&mut self) {
+        self.indices.clear();
+        self.traces.clear();
+    }
+}
+
+/// TD(λ) prediction of state values with eligibility traces (backward view).
+///
+/// Every step the TD error `r + γ V(s') - V(s)` is applied to all states in proportion to
+/// their trace, which is raised on every visit and decays by `γλ` each step.
+/// The selector is handed the state value store, as with `AgentNStepTd`, so it should follow a
+/// fixed policy that ignores it.
+pub struct AgentTdLambda<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> {
+    environment: T,
+    selector: U,
+    state_value_store: S,
+    store_action_count: S,
+    traces: EligibilityTraces<T::State, T::Action>,
+    config: TdLambdaConfig,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentTdLambda<T, U, S> {
+    ///
+    /// Create a new TD(λ) agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - policy whose state values are estimated
+    /// * `state_value_store` - S - store for state value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `config` - TdLambdaConfig - trace type, λ, step size and discount
+    pub fn new(
+        environment: T,
+        selector: U,
+        state_value_store: S,
+        store_action_count: S,
+        config: TdLambdaConfig
+    ) -> AgentTdLambda<T, U, S> {
+        AgentTdLambda {
+            environment,
+            selector,
+            state_value_store,
+            store_action_count,
+            traces: EligibilityTraces::new(),
+            config,
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_state_value_estimate(&self, state: T::State) -> f64 {
+        let id = self.state_value_store.generate_id(state, None);
+        self.state_value_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentTdLambda<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        self.selector.select_action(
+            &mut self.environment,
+            &self.state_value_store,
+            &self.store_action_count
+        )
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Applies the TD error of the step to every state visited this episode, in proportion to
+    /// its trace
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        _action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        let next_value_estimate = if is_terminal {
+            0.0
+        } else {
+            self.get_state_value_estimate(self.environment.get_state())
+        };
+        let td_error =
+            reward +
+            self.config.discount * next_value_estimate -
+            self.get_state_value_estimate(state.clone());
+        self.traces.decay(self.config.discount * self.config.lambda);
+        let id = self.state_value_store.generate_id(state, None);
+        self.traces.mark_visit(id, self.config.trace_type, self.config.step_size);
+        self.traces.apply(&mut self.state_value_store, self.config.step_size * td_error);
+        if is_terminal {
+            self.traces.clear();
+        }
+    }
+
+    fn reset(&mut self) {
+        self.traces.clear();
+        self.environment.reset();
+    }
+}
+
+/// True online TD(λ) prediction of state values.
+///
+/// Uses dutch traces together with a correction for the change in the value of the current
+/// state since it was last bootstrapped from, which makes the updates match the online
+/// λ-return algorithm exactly.
+pub struct AgentTrueOnlineTd<
+    T: Environment,
+    U: Selector,
+    S: StateActionStore<T::State, T::Action>
+> {
+    environment: T,
+    selector: U,
+    state_value_store: S,
+    store_action_count: S,
+    traces: EligibilityTraces<T::State, T::Action>,
+    lambda: f64,
+    step_size: f64,
+    discount: f64,
+    /// value of the current state when it was bootstrapped from on the previous step
+    previous_value_estimate: f64,
+    total_actions_taken: usize,
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> AgentTrueOnlineTd<
+    T,
+    U,
+    S
+> {
+    ///
+    /// Create a new true online TD(λ) agent
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - T - environment to act in
+    /// * `selector` - U - policy whose state values are estimated
+    /// * `state_value_store` - S - store for state value estimates
+    /// * `store_action_count` - S - store for the number of times each state action pair was taken
+    /// * `lambda` - f64 - trace decay rate
+    /// * `step_size` - f64 - step size (alpha) of the update
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    pub fn new(
+        environment: T,
+        selector: U,
+        state_value_store: S,
+        store_action_count: S,
+        lambda: f64,
+        step_size: f64,
+        discount: f64
+    ) -> AgentTrueOnlineTd<T, U, S> {
+        AgentTrueOnlineTd {
+            environment,
+            selector,
+            state_value_store,
+            store_action_count,
+            traces: EligibilityTraces::new(),
+            lambda,
+            step_size,
+            discount,
+            previous_value_estimate: 0.0,
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_state_value_estimate(&self, state: T::State) -> f64 {
+        let id = self.state_value_store.generate_id(state, None);
+        self.state_value_store.get_float(&id)
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+}
+
+impl<T: Environment, U: Selector, S: StateActionStore<T::State, T::Action>> Agent
+for AgentTrueOnlineTd<T, U, S> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        self.selector.select_action(
+            &mut self.environment,
+            &self.state_value_store,
+            &self.store_action_count
+        )
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        take_counted_action(&mut self.environment, &mut self.store_action_count, action)
+    }
+
+    /// Applies the TD error plus the change in the value of the state since it was last
+    /// bootstrapped from, which keeps the updates equal to the online λ-return algorithm
+    fn update_estimate(
+        &mut self,
+        state: T::State,
+        _action: T::Action,
+        reward: f64,
+        is_terminal: bool
+    ) {
+        let value_estimate = self.get_state_value_estimate(state.clone());
+        let next_value_estimate = if is_terminal {
+            0.0
+        } else {
+            self.get_state_value_estimate(self.environment.get_state())
+        };
+        let td_error = reward + self.discount * next_value_estimate - value_estimate;
+        self.traces.decay(self.discount * self.lambda);
+        let id = self.state_value_store.generate_id(state, None);
+        self.traces.mark_visit(id.clone(), TraceType::Dutch, self.step_size);
+        let correction = value_estimate - self.previous_value_estimate;
+        self.traces.apply(&mut self.state_value_store, self.step_size * (td_error + correction));
+        self.state_value_store.increment_float(id, -self.step_size * correction);
+        self.previous_value_estimate = next_value_estimate;
+        if is_terminal {
+            self.traces.clear();
+            self.previous_value_estimate = 0.0;
+        }
+    }
+
+    fn reset(&mut self) {
+        self.traces.clear();
+        self.previous_value_estimate = 0.0;
+        self.environment.reset();
+    }
+}
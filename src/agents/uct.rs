@@ -0,0 +1,300 @@
+use rand::{ rngs::StdRng, seq::SliceRandom, Rng, SeedableRng };
+
+use crate::environment::{ Environment, Snapshot };
+
+use super::agent::Agent;
+
+struct Edge<S, A> {
+    action: A,
+    visits: f64,
+    total_return: f64,
+    // every state the action led to, with the node of that state
+    outcomes: Vec<(S, usize)>,
+}
+
+struct Node<S, A> {
+    visits: f64,
+    untried_actions: Vec<A>,
+    edges: Vec<Edge<S, A>>,
+}
+
+impl<S, A> Node<S, A> {
+    fn new<T: Environment<State = S, Action = A>>(environment: &T) -> Node<S, A> {
+        Node {
+            visits: 0.0,
+            untried_actions: environment.get_actions(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+/// Monte Carlo Tree Search with UCB1 selection (UCT).
+///
+/// Every simulation starts from a snapshot of the environment, walks down the search tree
+/// picking edges by UCB1, expands one new node, finishes the episode with a rollout and backs
+/// the return up along the path. Stochastic environments are handled by keeping a child node
+/// for every state an action has led to.
+///
+/// Returns are from the perspective of the player acting at each node. In multi-player
+/// environments the game is assumed to be zero sum, so a return for one player is the negated
+/// return for the other.
+pub struct UctPlanner {
+    simulations: usize,
+    exploration: f64,
+    discount: f64,
+    max_depth: usize,
+    rng: StdRng,
+}
+
+impl UctPlanner {
+    ///
+    /// Create a new planner
+    ///
+    /// # Arguments
+    ///
+    /// * `simulations` - usize - number of simulations run for every move
+    /// * `exploration` - f64 - exploration constant of UCB1, sqrt(2) for rewards in [0, 1]
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    /// * `max_depth` - usize - maximum number of steps of a simulation
+    pub fn new(simulations: usize, exploration: f64, discount: f64, max_depth: usize) -> UctPlanner {
+        UctPlanner::with_rng(simulations, exploration, discount, max_depth, StdRng::from_entropy())
+    }
+
+    ///
+    /// Create a planner whose simulations are reproducible
+    ///
+    /// # Arguments
+    ///
+    /// * `simulations` - usize - number of simulations run for every move
+    /// * `exploration` - f64 - exploration constant of UCB1
+    /// * `discount` - f64 - discount factor (gamma) of future rewards
+    /// * `max_depth` - usize - maximum number of steps of a simulation
+    /// * `seed` - u64 - seed for snapshots, expansion and rollouts
+    pub fn with_seed(
+        simulations: usize,
+        exploration: f64,
+        discount: f64,
+        max_depth: usize,
+        seed: u64
+    ) -> UctPlanner {
+        UctPlanner::with_rng(
+            simulations,
+            exploration,
+            discount,
+            max_depth,
+            StdRng::seed_from_u64(seed)
+        )
+    }
+
+    fn with_rng(
+        simulations: usize,
+        exploration: f64,
+        discount: f64,
+        max_depth: usize,
+        rng: StdRng
+    ) -> UctPlanner {
+        assert!(simulations > 0, "at least one simulation must be run for every move");
+        assert!(max_depth > 0, "simulations must be allowed at least one step");
+        UctPlanner {
+            simulations,
+            exploration,
+            discount,
+            max_depth,
+            rng,
+        }
+    }
+
+    /// Search with uniformly random rollouts and return the most visited action,
+    /// panics when the environment is terminal
+    pub fn search<T: Snapshot>(&mut self, environment: &T) -> T::Action {
+        self.search_with_rollout(environment, |environment: &T, rng: &mut StdRng| {
+            environment.get_actions().choose(rng).expect("no actions available").clone()
+        })
+    }
+
+    ///
+    /// Search and return the most visited action. Panics when the environment is terminal,
+    /// as there is no action to pick.
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - &T - environment in the state to plan from
+    /// * `rollout_policy` - P - picks actions once a simulation leaves the tree
+    pub fn search_with_rollout<T: Snapshot, P: FnMut(&T, &mut StdRng) -> T::Action>(
+        &mut self,
+        environment: &T,
+        mut rollout_policy: P
+    ) -> T::Action {
+        assert!(!environment.is_terminal(), "cannot search from a terminal state");
+        let mut nodes = vec![Node::new(environment)];
+        for _ in 0..self.simulations {
+            self.simulate(&mut nodes, environment, &mut rollout_policy);
+        }
+        let root = &nodes[0];
+        let best_edge = root.edges
+            .iter()
+            .max_by(|a, b| a.visits.total_cmp(&b.visits))
+            .expect("every simulation takes a step from a non-terminal root");
+        best_edge.action.clone()
+    }
+
+    fn simulate<T: Snapshot, P: FnMut(&T, &mut StdRng) -> T::Action>(
+        &mut self,
+        nodes: &mut Vec<Node<T::State, T::Action>>,
+        root: &T,
+        rollout_policy: &mut P
+    ) {
+        let mut environment = root.snapshot(self.rng.gen());
+        // (node, edge) taken at every step inside the tree
+        let mut path: Vec<(usize, usize)> = Vec::new();
+        // (player, reward) of every step of the simulation
+        let mut steps: Vec<(usize, f64)> = Vec::new();
+        let mut node = 0;
+
+        // selection and expansion
+        while !environment.is_terminal() && steps.len() < self.max_depth {
+            let edge = if !nodes[node].untried_actions.is_empty() {
+                let untried_actions = &mut nodes[node].untried_actions;
+                let index = self.rng.gen_range(0..untried_actions.len());
+                let action = untried_actions.swap_remove(index);
+                nodes[node].edges.push(Edge {
+                    action,
+                    visits: 0.0,
+                    total_return: 0.0,
+                    outcomes: Vec::new(),
+                });
+                nodes[node].edges.len() - 1
+            } else if !nodes[node].edges.is_empty() {
+                self.select_edge(&nodes[node])
+            } else {
+                break;
+            };
+            let player = environment.get_current_player();
+            let reward = environment.step(nodes[node].edges[edge].action.clone());
+            path.push((node, edge));
+            steps.push((player, reward));
+
+            let state = environment.get_state();
+            let outcome = nodes[node].edges[edge].outcomes
+                .iter()
+                .find(|(outcome_state, _)| *outcome_state == state)
+                .map(|(_, child)| *child);
+            match outcome {
+                Some(child) => {
+                    node = child;
+                }
+                None => {
+                    // expand a single new node per simulation, then roll out
+                    nodes.push(Node::new(&environment));
+                    let child = nodes.len() - 1;
+                    nodes[node].edges[edge].outcomes.push((state, child));
+                    break;
+                }
+            }
+        }
+
+        // rollout
+        while !environment.is_terminal() && steps.len() < self.max_depth {
+            let player = environment.get_current_player();
+            let action = rollout_policy(&environment, &mut self.rng);
+            let reward = environment.step(action);
+            steps.push((player, reward));
+        }
+
+        // backpropagation
+        let returns = self.returns(&steps);
+        for ((node, edge), g) in path.into_iter().zip(returns) {
+            nodes[node].visits += 1.0;
+            let edge = &mut nodes[node].edges[edge];
+            edge.visits += 1.0;
+            edge.total_return += g;
+        }
+    }
+
+    /// Edge with the largest UCB1 score, ties go to the first such edge
+    fn select_edge<S, A>(&self, node: &Node<S, A>) -> usize {
+        let log_visits = node.visits.ln();
+        let mut max = f64::NEG_INFINITY;
+        let mut max_index = 0;
+        for (i, edge) in node.edges.iter().enumerate() {
+            let score =
+                edge.total_return / edge.visits +
+                self.exploration * (log_visits / edge.visits).sqrt();
+            if score > max {
+                max = score;
+                max_index = i;
+            }
+        }
+        max_index
+    }
+
+    /// Discounted return from every step, for the player who acted at that step
+    fn returns(&self, steps: &[(usize, f64)]) -> Vec<f64> {
+        let mut returns = vec![0.0; steps.len()];
+        let mut g = 0.0;
+        let mut next_player: Option<usize> = None;
+        for (t, (player, reward)) in steps.iter().enumerate().rev() {
+            let future = match next_player {
+                Some(next_player) if next_player != *player => -g,
+                _ => g,
+            };
+            g = reward + self.discount * future;
+            returns[t] = g;
+            next_player = Some(*player);
+        }
+        returns
+    }
+}
+
+/// Agent that plans every move with a fresh UCT search of its environment
+pub struct AgentUct<T: Snapshot> {
+    environment: T,
+    planner: UctPlanner,
+    total_actions_taken: usize,
+}
+
+impl<T: Snapshot> AgentUct<T> {
+    pub fn new(environment: T, planner: UctPlanner) -> AgentUct<T> {
+        AgentUct {
+            environment,
+            planner,
+            total_actions_taken: 0,
+        }
+    }
+
+    pub fn get_total_actions_taken(&self) -> usize {
+        self.total_actions_taken
+    }
+}
+
+impl<T: Snapshot> Agent for AgentUct<T> {
+    type State = T::State;
+    type Action = T::Action;
+    type Environment = T;
+
+    fn get_environment(&self) -> &T {
+        &self.environment
+    }
+
+    fn select_action(&mut self) -> T::Action {
+        self.planner.search(&self.environment)
+    }
+
+    fn take_action(&mut self, action: T::Action) -> f64 {
+        self.total_actions_taken += 1;
+        self.environment.step(action)
+    }
+
+    // the search tree is rebuilt for every move, so there is nothing to learn
+    fn update_estimate(
+        &mut self,
+        _state: T::State,
+        _action: T::Action,
+        _reward: f64,
+        _is_terminal: bool
+    ) {}
+
+    fn reset(&mut self) {
+        self.environment.reset();
+    }
+}
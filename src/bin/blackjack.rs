@@ -1,8 +1,10 @@
 use rl_examples::{
     agents::mcts::AgentMcts,
-    environments::blackjack::{ decode_state, Blackjack },
+    environments::blackjack::{ Blackjack, BlackjackAction, BlackjackState },
     selectors::epsilon_greedy::EpsilonGreedySelector,
-    store::{ MemoryStore, Store },
+    seed::SeedStream,
+    store::{ DenseStore, StoreFormat },
+    trainer::{ Trainer, TrainingBudget },
 };
 use plotters::{
     backend::BitMapBackend,
@@ -13,16 +15,22 @@ use plotters::{
     style::{ Color, BLACK, GREEN, WHITE },
 };
 
+use std::path::Path;
+
+const EXPERIMENT_SEED: u64 = 0;
+
 // TODO: UPDATE GRAPH TO DEPICT VALUE ESTIMATES
 fn main() {
     println!("Running Blackjack!");
-    let blackjack = Blackjack::new();
+    let seeds = SeedStream::new(EXPERIMENT_SEED);
+    let blackjack = Blackjack::with_seed(seeds.child(0).seed());
     let epsilon = 0.1;
-    let selector = EpsilonGreedySelector::new(epsilon);
-    let q_store = MemoryStore::new();
-    let state_value_store = MemoryStore::new();
-    let store_action_count = MemoryStore::new();
-    let store_state_count = MemoryStore::new();
+    let selector = EpsilonGreedySelector::with_seed(epsilon, seeds.child(1).seed());
+    // blackjack states are enumerable so the tables can be laid out densely
+    let q_store = DenseStore::from_environment(&blackjack);
+    let state_value_store = DenseStore::from_environment(&blackjack);
+    let store_action_count = DenseStore::from_environment(&blackjack);
+    let store_state_count = DenseStore::from_environment(&blackjack);
     let mut agent = AgentMcts::new(
         blackjack,
         selector,
@@ -31,16 +39,25 @@ fn main() {
         store_action_count,
         store_state_count
     );
-    // now run the agent through independent episodes
-    let num_episodes: usize = 250000;
-    let mut all_rewards: Vec<f64> = vec![];
-    for i in 0..num_episodes {
-        println!("Running episode: {}", i + 1);
-
-        let total_reward: f64 = agent.run_episode();
-        println!("Total reward: {}", total_reward);
-        all_rewards.push(total_reward);
+    // every run saves a checkpoint, `--resume` continues training from the one the last run left
+    let checkpoint_directory = Path::new("checkpoints/blackjack");
+    let checkpoint_format = StoreFormat::Binary;
+    if std::env::args().skip(1).any(|arg| arg == "--resume") {
+        agent
+            .load_checkpoint(checkpoint_directory, checkpoint_format)
+            .unwrap_or_else(|error| {
+                panic!("no checkpoint to resume from at {}: {}", checkpoint_directory.display(), error)
+            });
+        println!("Resumed from checkpoint: {}", checkpoint_directory.display());
+    } else {
+        println!("Training from scratch, pass --resume to continue from the last checkpoint");
     }
+    // now run the agent through the episodes
+    let num_episodes: usize = 250000;
+    let trainer = Trainer::new(TrainingBudget::Episodes(num_episodes), 1);
+    let all_rewards = trainer.train(&mut agent).episode_returns;
+    agent.save_checkpoint(checkpoint_directory, checkpoint_format).unwrap();
+    println!("Checkpoint saved at: {}", checkpoint_directory.display());
     // get state value estimates
     get_value_estimates(&agent);
     // now plot the average rewards using plotters crate
@@ -71,11 +88,11 @@ fn main() {
     )
         .unwrap()
         .label("Total Rewards")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &GREEN));
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], GREEN));
 
     ctx.configure_series_labels()
-        .border_style(&BLACK)
-        .background_style(&WHITE.mix(0.8))
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
         .draw()
         .unwrap();
 
@@ -83,15 +100,18 @@ fn main() {
 }
 
 fn get_value_estimates(
-    agent: &AgentMcts<Blackjack, EpsilonGreedySelector, MemoryStore>
+    agent: &AgentMcts<
+        Blackjack,
+        EpsilonGreedySelector,
+        DenseStore<BlackjackState, BlackjackAction>
+    >
 ) -> Vec<f64> {
     let mut value_estimates: Vec<f64> = vec![];
     let states = agent.all_possible_states();
     for state in states {
-        let state_value = agent.get_state_value_estimate(state.clone());
-        let state_count = agent.get_state_visit_count(state.clone());
-        let state_str = decode_state(state.clone());
-        println!("State: {:?}, Value: {}, Count: {}", state_str, state_value, state_count);
+        let state_value = agent.get_state_value_estimate(state);
+        let state_count = agent.get_state_visit_count(state);
+        println!("State: {:?}, Value: {}, Count: {}", state, state_value, state_count);
         value_estimates.push(state_value);
     }
     value_estimates
@@ -0,0 +1,202 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    coord::combinators::IntoLogRange,
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, RED, WHITE },
+};
+use rand::{ rngs::StdRng, seq::SliceRandom, SeedableRng };
+use rl_examples::{
+    agents::off_policy_mc::{ AgentOffPolicyMc, ImportanceSampling, OffPolicyMcConfig, TargetPolicy },
+    dynamic_programming::{ evaluate_policy, policy_iteration, value_iteration, DpConfig, DpSolution, SweepMode },
+    environment::Environment,
+    environments::blackjack::{ Blackjack, BlackjackAction, BlackjackState },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ DenseStore, Store },
+};
+
+const EXPERIMENT_SEED: u64 = 0;
+
+// dealer cards in the order of the printed tables, the ace is showing as 11
+const DEALER_CARDS: [i32; 10] = [11, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+type BlackjackStore = DenseStore<BlackjackState, BlackjackAction>;
+
+// Exact blackjack values from the infinite deck model, used to check Monte Carlo estimates
+fn main() {
+    let blackjack = Blackjack::with_seed(SeedStream::new(EXPERIMENT_SEED).seed());
+    let states = blackjack.all_possible_states();
+
+    // evaluate the policy of Sutton & Barto Example 5.1 exactly
+    let mut exact_values: Option<BlackjackStore> = None;
+    for sweep_mode in [SweepMode::Synchronous, SweepMode::InPlace] {
+        let config = DpConfig {
+            sweep_mode,
+            ..DpConfig::default()
+        };
+        let (values, sweeps): (BlackjackStore, usize) = evaluate_policy(
+            &blackjack,
+            |state: &BlackjackState| vec![(stick_on_twenty(state), 1.0)],
+            config
+        );
+        println!("Policy evaluation ({:?}) converged in {} sweeps", sweep_mode, sweeps);
+        exact_values = Some(values);
+    }
+    let exact_values = exact_values.unwrap();
+    let start_state = BlackjackState {
+        player_sum: 13,
+        dealer_showing: 2,
+        usable_ace: true,
+    };
+    println!(
+        "Value of {:?} when sticking on 20: {:.5}",
+        start_state,
+        exact_values.get_float(&(start_state, None))
+    );
+
+    // Monte Carlo estimates of the same policy from random behavior, compared state by state.
+    // Episodes start in a random state, as usable aces are rarely dealt.
+    let independent_runs = 10;
+    let num_episodes = 100000;
+    let measure_every = 100;
+    let num_measurements = num_episodes / measure_every;
+    let mut ordinary_errors = vec![0.0; num_measurements];
+    let mut weighted_errors = vec![0.0; num_measurements];
+    for r in 0..independent_runs {
+        let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+        for (sampling, errors) in [
+            (ImportanceSampling::Ordinary, &mut ordinary_errors),
+            (ImportanceSampling::Weighted, &mut weighted_errors),
+        ] {
+            let mut agent = AgentOffPolicyMc::new(
+                Blackjack::with_seed(seeds.child(0).seed()),
+                EpsilonGreedySelector::with_seed(1.0, seeds.child(1).seed()),
+                TargetPolicy::Fixed(Box::new(stick_on_twenty)),
+                BlackjackStore::new(),
+                BlackjackStore::new(),
+                BlackjackStore::new(),
+                OffPolicyMcConfig {
+                    sampling,
+                    discount: 1.0,
+                }
+            );
+            let mut rng = StdRng::seed_from_u64(seeds.child(2).seed());
+            for error in errors.iter_mut() {
+                for _ in 0..measure_every {
+                    agent.run_episode_from_state(states.choose(&mut rng).unwrap());
+                }
+                let square_error: f64 = states
+                    .iter()
+                    .map(|state| {
+                        let exact_value = exact_values.get_float(&(*state, None));
+                        (agent.get_state_value_estimate(*state) - exact_value).powi(2)
+                    })
+                    .sum();
+                let rms_error = (square_error / (states.len() as f64)).sqrt();
+                *error += rms_error / (independent_runs as f64);
+            }
+        }
+    }
+    println!(
+        "RMS error over all states after {} episodes - ordinary: {:.4}, weighted: {:.4}",
+        num_episodes,
+        ordinary_errors.last().unwrap(),
+        weighted_errors.last().unwrap()
+    );
+
+    // both planners should agree on the optimal policy of Sutton & Barto Figure 5.2
+    let value_solution: DpSolution<_, _, BlackjackStore> = value_iteration(
+        &blackjack,
+        DpConfig::default()
+    );
+    let policy_solution: DpSolution<_, _, BlackjackStore> = policy_iteration(
+        &blackjack,
+        DpConfig::default()
+    );
+    println!(
+        "\nValue iteration: {} sweeps, policy iteration: {} sweeps and {} improvements",
+        value_solution.sweeps,
+        policy_solution.sweeps,
+        policy_solution.improvements
+    );
+    let disagreements = states
+        .iter()
+        .filter(|state| value_solution.policy[state] != policy_solution.policy[state])
+        .count();
+    println!("States where the optimal policies disagree: {}", disagreements);
+    for usable_ace in [true, false] {
+        println!("\nUsable ace: {} (S = stick, H = hit)", usable_ace);
+        println!("     A  2  3  4  5  6  7  8  9 10");
+        for player_sum in (12..22).rev() {
+            let row: Vec<&str> = DEALER_CARDS.iter()
+                .map(|dealer_showing| {
+                    let state = BlackjackState {
+                        player_sum,
+                        dealer_showing: *dealer_showing,
+                        usable_ace,
+                    };
+                    match value_solution.policy[&state] {
+                        BlackjackAction::Stick => " S",
+                        BlackjackAction::Hit => " H",
+                    }
+                })
+                .collect();
+            println!("{:>3} {}", player_sum, row.join(" "));
+        }
+    }
+
+    let plot_location = "plots/blackjack_dp.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Monte Carlo vs Exact Blackjack Values", ("sans-serif", 30))
+        .build_cartesian_2d(
+            ((measure_every as f64)..(num_episodes as f64)).log_scale(),
+            0.0..1.0
+        )
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("Episodes (log scale)")
+        .y_desc("RMS error over all states")
+        .draw()
+        .unwrap();
+
+    for (errors, color, label) in [
+        (&ordinary_errors, RED, "Ordinary importance sampling"),
+        (&weighted_errors, BLUE, "Weighted importance sampling"),
+    ] {
+        ctx.draw_series(
+            LineSeries::new(
+                errors
+                    .iter()
+                    .enumerate()
+                    .map(|(i, error)| (((i + 1) * measure_every) as f64, *error)),
+                &color
+            )
+        )
+            .unwrap()
+            .label(label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("\nPlot saved at: {}", plot_location);
+}
+
+/// Policy whose values are estimated, sticks only on 20 or 21
+fn stick_on_twenty(state: &BlackjackState) -> BlackjackAction {
+    if state.player_sum >= 20 { BlackjackAction::Stick } else { BlackjackAction::Hit }
+}
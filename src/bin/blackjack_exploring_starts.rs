@@ -0,0 +1,121 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::Rectangle,
+    style::{ Color, BLUE, WHITE },
+};
+use rand::{ rngs::StdRng, SeedableRng };
+use rl_examples::{
+    agents::mcts::{ AgentMcts, MctsConfig, VisitMode },
+    environments::blackjack::{ Blackjack, BlackjackAction, BlackjackState },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::DenseStore,
+};
+
+const EXPERIMENT_SEED: u64 = 0;
+
+type BlackjackAgent = AgentMcts<
+    Blackjack,
+    EpsilonGreedySelector,
+    DenseStore<BlackjackState, BlackjackAction>
+>;
+
+// Monte Carlo ES on blackjack, reproducing the optimal policy of Sutton & Barto Figure 5.2
+fn main() {
+    let num_episodes = 500000;
+    let seeds = SeedStream::new(EXPERIMENT_SEED);
+    let blackjack = Blackjack::with_seed(seeds.child(0).seed());
+    // exploring starts take care of exploration, so the policy is greedy
+    let selector = EpsilonGreedySelector::with_seed(0.0, seeds.child(1).seed());
+    let q_store = DenseStore::from_environment(&blackjack);
+    let state_value_store = DenseStore::from_environment(&blackjack);
+    let store_action_count = DenseStore::from_environment(&blackjack);
+    let store_state_count = DenseStore::from_environment(&blackjack);
+    let mut agent = AgentMcts::with_config(
+        blackjack,
+        selector,
+        q_store,
+        state_value_store,
+        store_action_count,
+        store_state_count,
+        MctsConfig {
+            visit_mode: VisitMode::FirstVisit,
+            ..MctsConfig::default()
+        }
+    );
+    let mut rng = StdRng::seed_from_u64(seeds.child(2).seed());
+    for i in 0..num_episodes {
+        if (i + 1) % 100000 == 0 {
+            println!("Episode: {}", i + 1);
+        }
+        agent.run_episode_with_exploring_start(&mut rng);
+    }
+
+    for usable_ace in [true, false] {
+        println!("\nUsable ace: {} (S = stick, H = hit)", usable_ace);
+        println!("     A  2  3  4  5  6  7  8  9 10");
+        for player_sum in (12..22).rev() {
+            let row: Vec<&str> = DEALER_CARDS.iter()
+                .map(|dealer_showing| {
+                    let state = BlackjackState {
+                        player_sum,
+                        dealer_showing: *dealer_showing,
+                        usable_ace,
+                    };
+                    if should_stick(&agent, state) { " S" } else { " H" }
+                })
+                .collect();
+            println!("{:>3} {}", player_sum, row.join(" "));
+        }
+    }
+
+    let plot_location = "plots/blackjack_exploring_starts.png";
+    let root_area = BitMapBackend::new(plot_location, (800, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+    let panels = root_area.split_evenly((1, 2));
+    for (panel, usable_ace) in panels.iter().zip([true, false]) {
+        let caption = if usable_ace { "Usable ace" } else { "No usable ace" };
+        // dealer cards on the x axis with the ace as 1, sticking cells are filled
+        let mut ctx = ChartBuilder::on(panel)
+            .set_label_area_size(LabelAreaPosition::Left, 40)
+            .set_label_area_size(LabelAreaPosition::Bottom, 40)
+            .caption(caption, ("sans-serif", 30))
+            .margin(10)
+            .build_cartesian_2d(1..11, 12..22)
+            .unwrap();
+        ctx.configure_mesh()
+            .x_desc("Dealer showing")
+            .y_desc("Player sum")
+            .disable_mesh()
+            .draw()
+            .unwrap();
+        for player_sum in 12..22 {
+            for (column, dealer_showing) in DEALER_CARDS.iter().enumerate() {
+                let state = BlackjackState {
+                    player_sum,
+                    dealer_showing: *dealer_showing,
+                    usable_ace,
+                };
+                if should_stick(&agent, state) {
+                    let x = (column as i32) + 1;
+                    ctx.draw_series(
+                        std::iter::once(
+                            Rectangle::new([(x, player_sum), (x + 1, player_sum + 1)], BLUE.filled())
+                        )
+                    ).unwrap();
+                }
+            }
+        }
+    }
+    println!("\nPlot saved at: {}", plot_location);
+}
+
+// dealer cards in the order of the book, an ace is showing as 11
+const DEALER_CARDS: [i32; 10] = [11, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+
+fn should_stick(agent: &BlackjackAgent, state: BlackjackState) -> bool {
+    agent.get_q_estimate(state, BlackjackAction::Stick) >=
+        agent.get_q_estimate(state, BlackjackAction::Hit)
+}
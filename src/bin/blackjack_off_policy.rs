@@ -0,0 +1,125 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    coord::combinators::IntoLogRange,
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, RED, WHITE },
+};
+use rl_examples::{
+    agents::off_policy_mc::{ AgentOffPolicyMc, ImportanceSampling, OffPolicyMcConfig, TargetPolicy },
+    environments::blackjack::{ Blackjack, BlackjackAction, BlackjackState },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+};
+
+// both estimators see the same cards and behavior actions in every run
+const EXPERIMENT_SEED: u64 = 0;
+
+// value of the start state under the target policy, from Sutton & Barto
+const TRUE_VALUE: f64 = -0.27726;
+
+// Off-policy estimation of a single blackjack state, reproducing Sutton & Barto Figure 5.3
+fn main() {
+    let independent_runs = 100;
+    let num_episodes = 10000;
+    let start_state = BlackjackState {
+        player_sum: 13,
+        dealer_showing: 2,
+        usable_ace: true,
+    };
+
+    let mut ordinary_errors = vec![0.0; num_episodes];
+    let mut weighted_errors = vec![0.0; num_episodes];
+    for r in 0..independent_runs {
+        let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+        for (sampling, errors) in [
+            (ImportanceSampling::Ordinary, &mut ordinary_errors),
+            (ImportanceSampling::Weighted, &mut weighted_errors),
+        ] {
+            // the behavior policy hits or sticks with equal probability
+            let mut agent = AgentOffPolicyMc::new(
+                Blackjack::with_seed(seeds.child(0).seed()),
+                EpsilonGreedySelector::with_seed(1.0, seeds.child(1).seed()),
+                TargetPolicy::Fixed(Box::new(stick_on_twenty)),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                OffPolicyMcConfig {
+                    sampling,
+                    discount: 1.0,
+                }
+            );
+            for error in errors.iter_mut() {
+                agent.run_episode_from_state(&start_state);
+                let estimate = agent.get_state_value_estimate(start_state);
+                *error += (estimate - TRUE_VALUE).powi(2) / (independent_runs as f64);
+            }
+        }
+    }
+    println!(
+        "Mean square error after {} episodes - ordinary: {:.4}, weighted: {:.4}",
+        num_episodes,
+        ordinary_errors.last().unwrap(),
+        weighted_errors.last().unwrap()
+    );
+
+    let plot_location = "plots/blackjack_off_policy.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Ordinary vs Weighted Importance Sampling", ("sans-serif", 30))
+        .build_cartesian_2d((1.0..num_episodes as f64).log_scale(), 0.0..5.0)
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("Episodes (log scale)")
+        .y_desc("Mean square error")
+        .draw()
+        .unwrap();
+
+    ctx.draw_series(
+        LineSeries::new(
+            ordinary_errors
+                .iter()
+                .enumerate()
+                .map(|(i, error)| ((i + 1) as f64, *error)),
+            &RED
+        )
+    )
+        .unwrap()
+        .label("Ordinary importance sampling")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    ctx.draw_series(
+        LineSeries::new(
+            weighted_errors
+                .iter()
+                .enumerate()
+                .map(|(i, error)| ((i + 1) as f64, *error)),
+            &BLUE
+        )
+    )
+        .unwrap()
+        .label("Weighted importance sampling")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+/// Target policy of the experiment, sticks only on 20 or 21
+fn stick_on_twenty(state: &BlackjackState) -> BlackjackAction {
+    if state.player_sum >= 20 { BlackjackAction::Stick } else { BlackjackAction::Hit }
+}
@@ -0,0 +1,114 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, RED, WHITE },
+};
+use rl_examples::{
+    agents::{ expected_sarsa::AgentExpectedSarsa, sarsa::AgentSarsa },
+    environments::blackjack::Blackjack,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// both agents see the same sequence of cards and exploration seeds
+const EXPERIMENT_SEED: u64 = 0;
+
+fn main() {
+    let independent_runs = 50;
+    let num_episodes = 10000;
+    // number of episodes averaged into each point of the plot
+    let window = 250;
+    let epsilon = 0.1;
+    let step_size = 0.1;
+    let discount = 1.0;
+    let trainer = Trainer::new(TrainingBudget::Episodes(num_episodes), independent_runs);
+
+    println!("Running SARSA");
+    let sarsa_returns = trainer
+        .run(|r| {
+            let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+            AgentSarsa::new(
+                Blackjack::with_seed(seeds.child(0).seed()),
+                EpsilonGreedySelector::with_seed(epsilon, seeds.child(1).seed()),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                step_size,
+                discount
+            )
+        })
+        .average_episode_returns();
+
+    println!("Running Expected SARSA");
+    let expected_sarsa_returns = trainer
+        .run(|r| {
+            let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+            AgentExpectedSarsa::new(
+                Blackjack::with_seed(seeds.child(0).seed()),
+                EpsilonGreedySelector::with_seed(epsilon, seeds.child(1).seed()),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                step_size,
+                discount
+            )
+        })
+        .average_episode_returns();
+
+    let sarsa_returns = window_averages(&sarsa_returns, window);
+    let expected_sarsa_returns = window_averages(&expected_sarsa_returns, window);
+    println!(
+        "Average return over the last {} episodes - SARSA: {:.4}, Expected SARSA: {:.4}",
+        window,
+        sarsa_returns.last().unwrap().1,
+        expected_sarsa_returns.last().unwrap().1
+    );
+
+    let plot_location = "plots/blackjack_sarsa.png";
+    // now plot the average returns using plotters crate
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    // create a chart context
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("SARSA vs Expected SARSA", ("sans-serif", 40))
+        .build_cartesian_2d(0..num_episodes, -0.4..0.0)
+        .unwrap();
+
+    ctx.configure_mesh().x_desc("Episodes").y_desc("Average return").draw().unwrap();
+
+    // draw SARSA
+    ctx.draw_series(LineSeries::new(sarsa_returns, &BLUE))
+        .unwrap()
+        .label("SARSA")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    // draw Expected SARSA
+    ctx.draw_series(LineSeries::new(expected_sarsa_returns, &RED))
+        .unwrap()
+        .label("Expected SARSA")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+/// Average consecutive windows of values, keyed by the episode that ends each window
+fn window_averages(values: &[f64], window: usize) -> Vec<(usize, f64)> {
+    values
+        .chunks(window)
+        .enumerate()
+        .map(|(i, chunk)| ((i + 1) * window, chunk.iter().sum::<f64>() / (chunk.len() as f64)))
+        .collect()
+}
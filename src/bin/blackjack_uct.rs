@@ -0,0 +1,29 @@
+use rl_examples::{
+    agents::uct::{ AgentUct, UctPlanner },
+    environments::blackjack::Blackjack,
+    seed::SeedStream,
+    trainer::{ Trainer, TrainingBudget },
+};
+
+const EXPERIMENT_SEED: u64 = 0;
+
+fn main() {
+    let num_episodes = 2000;
+    for simulations in [10, 100, 1000] {
+        let seeds = SeedStream::new(EXPERIMENT_SEED);
+        let mut agent = AgentUct::new(
+            Blackjack::with_seed(seeds.child(0).seed()),
+            UctPlanner::with_seed(simulations, 1.0, 1.0, 21, seeds.child(1).seed())
+        );
+        let returns = Trainer::new(TrainingBudget::Episodes(num_episodes), 1)
+            .train(&mut agent)
+            .episode_returns;
+        let average_return = returns.iter().sum::<f64>() / (num_episodes as f64);
+        println!(
+            "Simulations per move: {}, average return over {} episodes: {:.4}",
+            simulations,
+            num_episodes,
+            average_return
+        );
+    }
+}
@@ -6,6 +6,29 @@ fn main() {
     for i in 0..10 {
         bandits.push(Bandit::new(i.to_string(), BanditType::Gaussian));
     }
+    // one machine of every other reward distribution
+    let machines = vec![
+        ("normal", BanditType::Normal { mean: 1.0, standard_deviation: 2.0 }),
+        ("bernoulli", BanditType::Bernoulli { probability: 0.05 }),
+        ("uniform", BanditType::Uniform { low: -1.0, high: 2.0 }),
+        ("exponential", BanditType::Exponential { rate: 2.0 }),
+        (
+            "student-t",
+            BanditType::StudentT { location: 0.5, scale: 1.0, degrees_of_freedom: 2.0 },
+        ),
+        ("cauchy", BanditType::Cauchy { location: 0.5, scale: 1.0 }),
+        (
+            "custom",
+            BanditType::Custom {
+                value: 0.25,
+                // a jackpot of 25 one pull in a hundred
+                sample: Box::new(|rng| if rand::Rng::gen_bool(rng, 0.01) { 25.0 } else { 0.0 }),
+            },
+        ),
+    ];
+    for (name, bandit_type) in machines {
+        bandits.push(Bandit::new(name.to_string(), bandit_type));
+    }
     for mut bandit in bandits {
         println!("{:?}", bandit);
         let reward = bandit.pull();
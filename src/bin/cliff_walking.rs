@@ -0,0 +1,154 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, RED, WHITE },
+};
+use rl_examples::{
+    agents::{ agent::Agent, q_learning::AgentQLearning, sarsa::AgentSarsa },
+    environment::Environment,
+    environments::gridworld::{ GridAction, Gridworld },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget, TrainingReport },
+};
+
+// both agents see the same exploration in every run
+const EXPERIMENT_SEED: u64 = 0;
+
+// SARSA vs Q-learning on cliff walking, reproducing Sutton & Barto Example 6.6
+fn main() {
+    let independent_runs = 500;
+    let num_episodes = 500;
+    let epsilon = 0.1;
+    let step_size = 0.5;
+    let discount = 1.0;
+
+    println!("Running SARSA");
+    let (sarsa_returns, sarsa_agent) = run_agent(independent_runs, num_episodes, |r| {
+        let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+        AgentSarsa::new(
+            Gridworld::cliff_walking(),
+            EpsilonGreedySelector::with_seed(epsilon, seeds.child(0).seed()).with_random_ties(),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            step_size,
+            discount
+        )
+    });
+    println!("Running Q-learning");
+    let (q_learning_returns, q_learning_agent) = run_agent(independent_runs, num_episodes, |r| {
+        let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+        AgentQLearning::new(
+            Gridworld::cliff_walking(),
+            EpsilonGreedySelector::with_seed(epsilon, seeds.child(0).seed()).with_random_ties(),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            step_size,
+            discount
+        )
+    });
+
+    // SARSA learns the safe path away from the cliff, Q-learning the optimal one along its edge
+    let gridworld = Gridworld::cliff_walking();
+    let actions = gridworld.get_actions();
+    let sarsa_path = gridworld.follow_policy(
+        |state| greedy_action(&actions, |action| sarsa_agent.get_q_estimate(*state, action)),
+        100
+    );
+    println!("\nSARSA greedy path ({} steps)", sarsa_path.len() - 1);
+    print!("{}", gridworld.render_path(&sarsa_path));
+    let q_learning_path = gridworld.follow_policy(
+        |state| greedy_action(&actions, |action| q_learning_agent.get_q_estimate(*state, action)),
+        100
+    );
+    println!("\nQ-learning greedy path ({} steps)", q_learning_path.len() - 1);
+    print!("{}", gridworld.render_path(&q_learning_path));
+
+    let plot_location = "plots/cliff_walking.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Cliff Walking", ("sans-serif", 40))
+        .build_cartesian_2d(0..num_episodes, -100.0..0.0)
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("Episodes")
+        .y_desc("Sum of rewards during episode")
+        .draw()
+        .unwrap();
+
+    for (returns, color, label) in [
+        (&sarsa_returns, BLUE, "SARSA"),
+        (&q_learning_returns, RED, "Q-learning"),
+    ] {
+        ctx.draw_series(
+            LineSeries::new(
+                returns
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (i, *v)),
+                &color
+            )
+        )
+            .unwrap()
+            .label(label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("\nPlot saved at: {}", plot_location);
+}
+
+///
+/// Train a fresh agent for every run
+/// Returns the return of every episode averaged across runs and the agent of the last run
+///
+/// # Arguments
+///
+/// * `independent_runs` - usize - number of runs
+/// * `num_episodes` - usize - number of episodes in each run
+/// * `create_agent` - F - called with the run index to create the agent for that run
+fn run_agent<A: Agent, F: FnMut(usize) -> A>(
+    independent_runs: usize,
+    num_episodes: usize,
+    mut create_agent: F
+) -> (Vec<f64>, A) {
+    let trainer = Trainer::new(TrainingBudget::Episodes(num_episodes), 1);
+    let mut report = TrainingReport::default();
+    let mut agent = create_agent(0);
+    report.runs.push(trainer.train(&mut agent));
+    for r in 1..independent_runs {
+        agent = create_agent(r);
+        report.runs.push(trainer.train(&mut agent));
+    }
+    (report.average_episode_returns(), agent)
+}
+
+/// Action with the highest estimate, ties go to the last action as with the greedy selectors
+fn greedy_action<F: Fn(GridAction) -> f64>(actions: &[GridAction], q_estimate: F) -> GridAction {
+    let mut max = f64::NEG_INFINITY;
+    let mut max_action = actions[0];
+    for action in actions {
+        let estimate = q_estimate(*action);
+        if estimate >= max {
+            max = estimate;
+            max_action = *action;
+        }
+    }
+    max_action
+}
+
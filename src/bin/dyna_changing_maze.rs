@@ -0,0 +1,100 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, RED, WHITE },
+};
+use rl_examples::{
+    agents::dyna_q::{ AgentDynaQ, DynaConfig },
+    environments::gridworld::Gridworld,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// both agents see the same exploration seeds
+const EXPERIMENT_SEED: u64 = 0;
+
+const INDEPENDENT_RUNS: usize = 20;
+
+// Dyna-Q vs Dyna-Q+ on mazes that change mid-run, reproducing Sutton & Barto Figures 8.4 and 8.5
+fn main() {
+    let plot_location = "plots/dyna_changing_maze.png";
+    let root_area = BitMapBackend::new(plot_location, (1000, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+    let panels = root_area.split_evenly((1, 2));
+
+    // name, maze, number of steps, number of planning steps and the top of the plot
+    let experiments = [
+        ("Blocking Maze", Gridworld::blocking_maze(1000), 3000, 10, 150.0),
+        ("Shortcut Maze", Gridworld::shortcut_maze(3000), 6000, 50, 400.0),
+    ];
+    for ((name, maze, num_steps, planning_steps, max_reward), panel) in experiments
+        .into_iter()
+        .zip(panels.iter()) {
+        let trainer = Trainer::new(TrainingBudget::Steps(num_steps), INDEPENDENT_RUNS);
+        let mut ctx = ChartBuilder::on(panel)
+            .set_label_area_size(LabelAreaPosition::Left, 40)
+            .set_label_area_size(LabelAreaPosition::Bottom, 40)
+            .caption(name, ("sans-serif", 24))
+            .margin(10)
+            .build_cartesian_2d(0..num_steps, 0.0..max_reward)
+            .unwrap();
+        ctx.configure_mesh().x_desc("Time steps").y_desc("Cumulative reward").draw().unwrap();
+
+        for (label, exploration_bonus, color) in [
+            ("Dyna-Q", None, BLUE),
+            ("Dyna-Q+", Some(1e-3), RED),
+        ] {
+            let rewards = trainer
+                .run(|r| {
+                    let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+                    AgentDynaQ::with_seed(
+                        maze.clone(),
+                        EpsilonGreedySelector::with_seed(0.1, seeds.child(0).seed())
+                            .with_random_ties(),
+                        MemoryStore::new(),
+                        MemoryStore::new(),
+                        DynaConfig {
+                            planning_steps,
+                            step_size: 1.0,
+                            discount: 0.95,
+                            exploration_bonus,
+                        },
+                        seeds.child(1).seed()
+                    )
+                })
+                .average_rewards();
+            let cumulative_rewards: Vec<(usize, f64)> = rewards
+                .iter()
+                .scan(0.0, |total, reward| {
+                    *total += reward;
+                    Some(*total)
+                })
+                .enumerate()
+                .map(|(i, total)| (i + 1, total))
+                .collect();
+            println!(
+                "{} - {}: cumulative reward {:.1}",
+                name,
+                label,
+                cumulative_rewards.last().unwrap().1
+            );
+            ctx.draw_series(LineSeries::new(cumulative_rewards, &color))
+                .unwrap()
+                .label(label)
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        }
+
+        ctx.configure_series_labels()
+            .border_style(BLACK)
+            .background_style(WHITE.mix(0.8))
+            .draw()
+            .unwrap();
+    }
+
+    println!("Plot saved at: {}", plot_location);
+}
@@ -0,0 +1,104 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, GREEN, RED, WHITE },
+};
+use rl_examples::{
+    agents::dyna_q::{ AgentDynaQ, DynaConfig },
+    environments::gridworld::Gridworld,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// every number of planning steps sees the same exploration seeds
+const EXPERIMENT_SEED: u64 = 0;
+
+// Dyna-Q with different numbers of planning steps, reproducing Sutton & Barto Figure 8.2
+fn main() {
+    let independent_runs = 30;
+    let num_episodes = 50;
+    let epsilon = 0.1;
+    let trainer = Trainer::new(TrainingBudget::Episodes(num_episodes), independent_runs);
+
+    let mut steps_per_episode = vec![];
+    for planning_steps in [0, 5, 50] {
+        let report = trainer.run(|r| {
+            let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+            AgentDynaQ::with_seed(
+                Gridworld::dyna_maze(),
+                EpsilonGreedySelector::with_seed(epsilon, seeds.child(0).seed())
+                    .with_random_ties(),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                DynaConfig {
+                    planning_steps,
+                    step_size: 0.1,
+                    discount: 0.95,
+                    exploration_bonus: None,
+                },
+                seeds.child(1).seed()
+            )
+        });
+        // the goal is the only reward, so episode lengths follow from the reward positions
+        let mut lengths = vec![0.0; num_episodes];
+        for run in report.runs.iter() {
+            let mut episode = 0;
+            for reward in run.rewards.iter() {
+                lengths[episode] += 1.0 / (independent_runs as f64);
+                if *reward > 0.0 {
+                    episode += 1;
+                }
+            }
+        }
+        println!(
+            "{} planning steps: {:.1} steps in the last episode",
+            planning_steps,
+            lengths.last().unwrap()
+        );
+        steps_per_episode.push((planning_steps, lengths));
+    }
+
+    let plot_location = "plots/dyna_maze.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Dyna-Q on the Dyna Maze", ("sans-serif", 30))
+        .build_cartesian_2d(2..num_episodes, 0.0..800.0)
+        .unwrap();
+
+    ctx.configure_mesh().x_desc("Episodes").y_desc("Steps per episode").draw().unwrap();
+
+    // the first episode is left out as it is the same for every number of planning steps
+    for ((planning_steps, lengths), color) in steps_per_episode.into_iter().zip([BLUE, GREEN, RED]) {
+        ctx.draw_series(
+            LineSeries::new(
+                lengths
+                    .into_iter()
+                    .enumerate()
+                    .skip(1)
+                    .map(|(i, length)| (i + 1, length)),
+                &color
+            )
+        )
+            .unwrap()
+            .label(format!("{} planning steps", planning_steps))
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
@@ -0,0 +1,65 @@
+use rl_examples::{
+    dynamic_programming::{
+        equiprobable_policy,
+        evaluate_policy,
+        policy_iteration,
+        value_iteration,
+        DpConfig,
+        DpSolution,
+        SweepMode,
+    },
+    environments::gridworld::{ GridAction, GridPosition, Gridworld },
+    store::{ MemoryStore, Store, StoreId },
+};
+
+type GridStore = MemoryStore<StoreId<GridPosition, GridAction>>;
+
+// Dynamic programming on the 4x4 gridworld, reproducing Sutton & Barto Figure 4.1
+fn main() {
+    let gridworld = Gridworld::dp_gridworld();
+    for sweep_mode in [SweepMode::Synchronous, SweepMode::InPlace] {
+        let config = DpConfig {
+            sweep_mode,
+            threshold: 1e-4,
+            ..DpConfig::default()
+        };
+        let (values, sweeps): (GridStore, usize) = evaluate_policy(
+            &gridworld,
+            equiprobable_policy(&gridworld),
+            config
+        );
+        println!(
+            "\nValues of the equiprobable policy ({:?}, {} sweeps)",
+            sweep_mode,
+            sweeps
+        );
+        print_values(&gridworld, &values);
+
+        let policy_solution: DpSolution<_, _, GridStore> = policy_iteration(&gridworld, config);
+        let value_solution: DpSolution<_, _, GridStore> = value_iteration(&gridworld, config);
+        println!(
+            "Policy iteration: {} sweeps and {} improvements, value iteration: {} sweeps",
+            policy_solution.sweeps,
+            policy_solution.improvements,
+            value_solution.sweeps
+        );
+    }
+
+    let value_solution: DpSolution<_, _, GridStore> = value_iteration(
+        &gridworld,
+        DpConfig::default()
+    );
+    println!("\nOptimal values");
+    print_values(&gridworld, &value_solution.values);
+    println!("\nOptimal policy");
+    print!("{}", gridworld.render_policy(|state| value_solution.policy[state]));
+}
+
+fn print_values(gridworld: &Gridworld, values: &GridStore) {
+    for row in 0..gridworld.get_rows() {
+        let row_values: Vec<String> = (0..gridworld.get_columns())
+            .map(|column| format!("{:>6.1}", values.get_float(&((row, column), None))))
+            .collect();
+        println!("{}", row_values.join(""));
+    }
+}
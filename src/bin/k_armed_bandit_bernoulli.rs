@@ -0,0 +1,131 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition, SeriesLabelPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, GREEN, RED, WHITE },
+};
+use rl_examples::{
+    agents::{ agent::Agent, q::AgentQ },
+    environments::bandit::{ BanditType, KArmedBandit },
+    selectors::{ epsilon_greedy::EpsilonGreedySelector, ucb::UCBSelector },
+    store::{ MemoryStore, Store },
+    seed::SeedStream,
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// every selector sees the same clicks
+const EXPERIMENT_SEED: u64 = 0;
+
+// click-through rates of the arms, the first one is the best
+const CLICK_THROUGH_RATES: [f64; 5] = [0.1, 0.04, 0.08, 0.05, 0.06];
+
+// Selectors on 0/1 rewards, like choosing which of several ads to show
+fn main() {
+    let independent_runs = 200;
+    let num_steps = 10000;
+    let trainer = Trainer::new(TrainingBudget::Steps(num_steps), independent_runs);
+
+    println!("Running epsilon greedy");
+    let epsilon_greedy_rates = optimal_action_rates(&trainer, |seeds| {
+        AgentQ::new(
+            create_bandit(seeds.child(0).seed()),
+            EpsilonGreedySelector::with_seed(0.1, seeds.child(1).seed()),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            MemoryStore::new()
+        )
+    });
+    println!("Running epsilon greedy with a small epsilon");
+    let small_epsilon_rates = optimal_action_rates(&trainer, |seeds| {
+        AgentQ::new(
+            create_bandit(seeds.child(0).seed()),
+            EpsilonGreedySelector::with_seed(0.01, seeds.child(1).seed()),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            MemoryStore::new()
+        )
+    });
+    println!("Running UCB");
+    let ucb_rates = optimal_action_rates(&trainer, |seeds| {
+        AgentQ::new(
+            create_bandit(seeds.child(0).seed()),
+            UCBSelector::new(0.1),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            MemoryStore::new()
+        )
+    });
+
+    let plot_location = "plots/k_armed_bandit_bernoulli.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Bernoulli Bandit", ("sans-serif", 40))
+        .build_cartesian_2d(0..num_steps, 0.0..100.0)
+        .unwrap();
+
+    ctx.configure_mesh().x_desc("Steps").y_desc("% Optimal action").draw().unwrap();
+
+    for (rates, color, label) in [
+        (&epsilon_greedy_rates, GREEN, "Epsilon = 0.1"),
+        (&small_epsilon_rates, BLUE, "Epsilon = 0.01"),
+        (&ucb_rates, RED, "UCB c = 0.1"),
+    ] {
+        ctx.draw_series(
+            LineSeries::new(
+                rates
+                    .iter()
+                    .enumerate()
+                    .map(|(i, rate)| (i, rate * 100.0)),
+                &color
+            )
+        )
+            .unwrap()
+            .label(label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+fn create_bandit(seed: u64) -> KArmedBandit {
+    let arms = CLICK_THROUGH_RATES.iter()
+        .map(|probability| BanditType::Bernoulli { probability: *probability })
+        .collect();
+    KArmedBandit::from_arms_with_seed(arms, seed)
+}
+
+///
+/// Fraction of runs that pulled the best arm at every step
+///
+/// # Arguments
+///
+/// * `trainer` - &Trainer - trainer running the independent runs
+/// * `create_agent` - F - called with the seeds of a run to create its agent
+fn optimal_action_rates<A: Agent, F: Fn(SeedStream) -> A>(
+    trainer: &Trainer,
+    create_agent: F
+) -> Vec<f64> {
+    let report = trainer.run(|r| create_agent(SeedStream::new(EXPERIMENT_SEED).child(r as u64)));
+    let rates = report.optimal_action_rates();
+    let tail = &rates[rates.len() / 2..];
+    println!(
+        "Best arm pulled in {:.1}% of the last {} steps",
+        (tail.iter().sum::<f64>() / (tail.len() as f64)) * 100.0,
+        tail.len()
+    );
+    rates
+}
@@ -0,0 +1,129 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition, SeriesLabelPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, CYAN, MAGENTA, RED, WHITE },
+};
+use rand::{ rngs::StdRng, SeedableRng };
+use rand_distr::{ Distribution, Normal };
+use rl_examples::{
+    agents::q::AgentQ,
+    environments::bandit::{ BanditType, KArmedBandit },
+    selectors::gradient_bandit::GradientBanditSelector,
+    store::{ MemoryStore, Store },
+    seed::SeedStream,
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// every configuration sees the same sequence of bandit problems
+const EXPERIMENT_SEED: u64 = 0;
+
+// mean of the true values, far enough from 0 that the baseline matters
+const VALUE_OFFSET: f64 = 4.0;
+
+// Gradient bandit with and without the average reward baseline, reproducing
+// Sutton & Barto Figure 2.5
+fn main() {
+    let k = 10;
+    let independent_runs = 2000;
+    let num_steps = 1000;
+    let configurations = [
+        (0.1, true, BLUE, "α = 0.1 with baseline"),
+        (0.4, true, CYAN, "α = 0.4 with baseline"),
+        (0.1, false, RED, "α = 0.1 without baseline"),
+        (0.4, false, MAGENTA, "α = 0.4 without baseline"),
+    ];
+    let mut series = vec![];
+    for (step_size, baseline, color, label) in configurations {
+        let rates = run_for_given_step_size(k, independent_runs, num_steps, step_size, baseline);
+        series.push((rates, color, label));
+    }
+
+    let plot_location = "plots/k_armed_bandit_gradient.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Gradient Bandit", ("sans-serif", 40))
+        .build_cartesian_2d(0..num_steps, 0.0..100.0)
+        .unwrap();
+
+    ctx.configure_mesh().x_desc("Steps").y_desc("% Optimal action").draw().unwrap();
+
+    for (rates, color, label) in series.iter() {
+        ctx.draw_series(
+            LineSeries::new(
+                rates
+                    .iter()
+                    .enumerate()
+                    .map(|(i, rate)| (i, rate * 100.0)),
+                color
+            )
+        )
+            .unwrap()
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], *color));
+    }
+
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+///
+/// Create a k armed bandit with unit variance rewards whose true values are drawn
+/// from a normal distribution with mean `VALUE_OFFSET` and variance 1
+///
+/// # Arguments
+///
+/// * `k` - usize - number of bandits
+/// * `seeds` - SeedStream - seeds for the bandits' true values and rewards
+fn create_bandit(k: usize, seeds: SeedStream) -> KArmedBandit {
+    let mut rng = StdRng::seed_from_u64(seeds.child(0).seed());
+    let normal = Normal::new(VALUE_OFFSET, 1.0).unwrap();
+    let arms = (0..k)
+        .map(|_| BanditType::Normal { mean: normal.sample(&mut rng), standard_deviation: 1.0 })
+        .collect();
+    KArmedBandit::from_arms_with_seed(arms, seeds.child(1).seed())
+}
+
+fn run_for_given_step_size(
+    k: usize,
+    independent_runs: usize,
+    num_steps: usize,
+    step_size: f64,
+    baseline: bool
+) -> Vec<f64> {
+    println!("Running for step size: {} with baseline: {}", step_size, baseline);
+    let trainer = Trainer::new(TrainingBudget::Steps(num_steps), independent_runs);
+    let report = trainer.run(|r| {
+        if r % 100 == 0 {
+            println!("Run: {}", r);
+        }
+        let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+        let mut selector = GradientBanditSelector::with_seed(step_size, seeds.child(1).seed());
+        if !baseline {
+            selector = selector.without_baseline();
+        }
+        AgentQ::new(
+            create_bandit(k, seeds.child(0)),
+            selector,
+            MemoryStore::new(),
+            MemoryStore::new(),
+            MemoryStore::new()
+        )
+    });
+    let rates = report.optimal_action_rates();
+    println!("Optimal action after {} steps: {:.1}%", num_steps, rates.last().unwrap() * 100.0);
+    rates
+}
@@ -0,0 +1,153 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition, SeriesLabelPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, RED, WHITE },
+};
+use rl_examples::{
+    agents::q::AgentQ,
+    environments::bandit::{ BanditType, KArmedBandit },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ MemoryStore, Store },
+    seed::SeedStream,
+    trainer::{ TrainingReport, Trainer, TrainingBudget },
+};
+
+// both estimators see the same sequence of bandit problems
+const EXPERIMENT_SEED: u64 = 0;
+
+// Sample averages against a constant step size on a bandit whose true values drift,
+// reproducing Sutton & Barto Exercise 2.5
+fn main() {
+    let k = 10;
+    let independent_runs = 500;
+    let num_steps = 10000;
+    let epsilon = 0.1;
+    let random_walk_standard_deviation = 0.01;
+    let sample_average_report = run_for_given_step_size(
+        k,
+        independent_runs,
+        num_steps,
+        epsilon,
+        random_walk_standard_deviation,
+        None
+    );
+    let constant_step_size_report = run_for_given_step_size(
+        k,
+        independent_runs,
+        num_steps,
+        epsilon,
+        random_walk_standard_deviation,
+        Some(0.1)
+    );
+    let series = [
+        (&sample_average_report, RED, "Sample averages"),
+        (&constant_step_size_report, BLUE, "Constant step size α = 0.1"),
+    ];
+
+    let plot_location = "plots/k_armed_bandit_nonstationary.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 800)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+    let panels = root_area.split_evenly((2, 1));
+
+    let mut ctx = ChartBuilder::on(&panels[0])
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Nonstationary Bandit", ("sans-serif", 40))
+        .build_cartesian_2d(0..num_steps, 0.0..2.0)
+        .unwrap();
+    ctx.configure_mesh().x_desc("Steps").y_desc("Average reward").draw().unwrap();
+    for (report, color, label) in series.iter() {
+        ctx.draw_series(
+            LineSeries::new(
+                report
+                    .average_rewards()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (i, *v)),
+                color
+            )
+        )
+            .unwrap()
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], *color));
+    }
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    let mut ctx = ChartBuilder::on(&panels[1])
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(0..num_steps, 0.0..100.0)
+        .unwrap();
+    ctx.configure_mesh().x_desc("Steps").y_desc("% Optimal action").draw().unwrap();
+    for (report, color, label) in series.iter() {
+        ctx.draw_series(
+            LineSeries::new(
+                report
+                    .optimal_action_rates()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, rate)| (i, rate * 100.0)),
+                color
+            )
+        )
+            .unwrap()
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], *color));
+    }
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+fn run_for_given_step_size(
+    k: usize,
+    independent_runs: usize,
+    num_steps: usize,
+    epsilon: f64,
+    random_walk_standard_deviation: f64,
+    step_size: Option<f64>
+) -> TrainingReport {
+    println!("Running for step size: {:?}", step_size);
+    let trainer = Trainer::new(TrainingBudget::Steps(num_steps), independent_runs);
+    let report = trainer.run(|r| {
+        if r % 100 == 0 {
+            println!("Run: {}", r);
+        }
+        let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+        // the true values start out equal and take independent random walks
+        let arms = (0..k)
+            .map(|_| BanditType::Normal { mean: 0.0, standard_deviation: 1.0 })
+            .collect();
+        let bandit = KArmedBandit::from_arms_with_seed(arms, seeds.child(0).seed()).with_random_walk(
+            random_walk_standard_deviation
+        );
+        let mut selector = EpsilonGreedySelector::with_seed(epsilon, seeds.child(1).seed());
+        if let Some(step_size) = step_size {
+            selector = selector.with_step_size(step_size);
+        }
+        AgentQ::new(bandit, selector, MemoryStore::new(), MemoryStore::new(), MemoryStore::new())
+    });
+    let average_rewards = report.average_rewards();
+    let tail = &average_rewards[num_steps / 2..];
+    println!(
+        "Average reward over the last {} steps: {:.3}",
+        tail.len(),
+        tail.iter().sum::<f64>() / (tail.len() as f64)
+    );
+    report
+}
@@ -1,101 +1,131 @@
 use plotters::{
     backend::BitMapBackend,
-    chart::{ ChartBuilder, LabelAreaPosition },
+    chart::{ ChartBuilder, LabelAreaPosition, SeriesLabelPosition },
     drawing::IntoDrawingArea,
     element::PathElement,
     series::LineSeries,
     style::{ Color, BLACK, BLUE, GREEN, RED, WHITE },
 };
 use rl_examples::{
-    agents::{ agent::Agent, q::AgentQ },
+    agents::q::AgentQ,
     environments::bandit::KArmedBandit,
     selectors::ucb::UCBSelector,
     store::{ MemoryStore, Store },
+    seed::SeedStream,
+    trainer::{ TrainingReport, Trainer, TrainingBudget },
 };
 
+// every configuration sees the same sequence of bandit problems
+const EXPERIMENT_SEED: u64 = 0;
+
+// Reward, % optimal action and cumulative regret of UCB action selection
 fn main() {
     let k = 10;
     let independent_runs = 2000;
     let num_steps = 1000;
-    let average_rewards_confidence_1 = run_for_given_confidence(
-        k,
-        independent_runs,
-        num_steps,
-        1.0
-    );
-    let average_rewards_confidence_2 = run_for_given_confidence(
-        k,
-        independent_runs,
-        num_steps,
-        2.0
-    );
-    let average_rewards_confidence_5 = run_for_given_confidence(
-        k,
-        independent_runs,
-        num_steps,
-        5.0
-    );
+    let confidence_1_report = run_for_given_confidence(k, independent_runs, num_steps, 1.0);
+    let confidence_2_report = run_for_given_confidence(k, independent_runs, num_steps, 2.0);
+    let confidence_5_report = run_for_given_confidence(k, independent_runs, num_steps, 5.0);
+    let series = [
+        (&confidence_1_report, GREEN, "Confidence = 1.0"),
+        (&confidence_2_report, BLUE, "Confidence = 2.0"),
+        (&confidence_5_report, RED, "Confidence = 5.0"),
+    ];
+    let max_cumulative_regret = series
+        .iter()
+        .flat_map(|(report, _, _)| report.cumulative_regrets())
+        .fold(0.0, f64::max);
+
     let plot_location = "plots/k_armed_bandit_ucb.png";
-    // now plot the average rewards using plotters crate
-    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    let root_area = BitMapBackend::new(plot_location, (600, 1200)).into_drawing_area();
     root_area.fill(&WHITE).unwrap();
+    let panels = root_area.split_evenly((3, 1));
 
-    // create a chart context
-    let mut ctx = ChartBuilder::on(&root_area)
+    let mut ctx = ChartBuilder::on(&panels[0])
         .set_label_area_size(LabelAreaPosition::Left, 40)
         .set_label_area_size(LabelAreaPosition::Right, 40)
         .set_label_area_size(LabelAreaPosition::Bottom, 40)
         .caption("Upper Confidence Bound", ("sans-serif", 40))
         .build_cartesian_2d(0..num_steps, 0.0..1.7)
         .unwrap();
-
-    ctx.configure_mesh().draw().unwrap();
-
-    // draw epsilon = 0.0
-    ctx.draw_series(
-        LineSeries::new(
-            average_rewards_confidence_1
-                .iter()
-                .enumerate()
-                .map(|(i, v)| (i, *v)),
-            &GREEN
+    ctx.configure_mesh().x_desc("Steps").y_desc("Average reward").draw().unwrap();
+    for (report, color, label) in series.iter() {
+        ctx.draw_series(
+            LineSeries::new(
+                report
+                    .average_rewards()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (i, *v)),
+                color
+            )
         )
-    )
-        .unwrap()
-        .label("Confidence = 1.0")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &GREEN));
+            .unwrap()
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], *color));
+    }
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
 
-    // draw epsilon = 0.1
-    ctx.draw_series(
-        LineSeries::new(
-            average_rewards_confidence_2
-                .iter()
-                .enumerate()
-                .map(|(i, v)| (i, *v)),
-            &BLUE
+    let mut ctx = ChartBuilder::on(&panels[1])
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(0..num_steps, 0.0..100.0)
+        .unwrap();
+    ctx.configure_mesh().x_desc("Steps").y_desc("% Optimal action").draw().unwrap();
+    for (report, color, label) in series.iter() {
+        ctx.draw_series(
+            LineSeries::new(
+                report
+                    .optimal_action_rates()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, rate)| (i, rate * 100.0)),
+                color
+            )
         )
-    )
-        .unwrap()
-        .label("Confidence = 2.0")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &BLUE));
+            .unwrap()
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], *color));
+    }
+    ctx.configure_series_labels()
+        .position(SeriesLabelPosition::LowerRight)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
 
-    // draw epsilon = 0.01
-    ctx.draw_series(
-        LineSeries::new(
-            average_rewards_confidence_5
-                .iter()
-                .enumerate()
-                .map(|(i, v)| (i, *v)),
-            &RED
+    let mut ctx = ChartBuilder::on(&panels[2])
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .build_cartesian_2d(0..num_steps, 0.0..max_cumulative_regret * 1.05)
+        .unwrap();
+    ctx.configure_mesh().x_desc("Steps").y_desc("Cumulative regret").draw().unwrap();
+    for (report, color, label) in series.iter() {
+        ctx.draw_series(
+            LineSeries::new(
+                report
+                    .cumulative_regrets()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| (i, *v)),
+                color
+            )
         )
-    )
-        .unwrap()
-        .label("Confidence = 5.0")
-        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], &RED));
-
+            .unwrap()
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], *color));
+    }
     ctx.configure_series_labels()
-        .border_style(&BLACK)
-        .background_style(&WHITE.mix(0.8))
+        .position(SeriesLabelPosition::UpperLeft)
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
         .draw()
         .unwrap();
 
@@ -107,45 +137,27 @@ fn run_for_given_confidence(
     independent_runs: usize,
     num_steps: usize,
     confidence: f64
-) -> Vec<f64> {
+) -> TrainingReport {
     println!("Running for confidence: {}", confidence);
-    let mut all_rewards: Vec<Vec<f64>> = vec![];
-    // state is fixed for this problem
-    let state = "0".to_string();
-    for r in 0..independent_runs {
+    let trainer = Trainer::new(TrainingBudget::Steps(num_steps), independent_runs);
+    let report = trainer.run(|r| {
         if r % 100 == 0 {
             println!("Run: {}", r);
         }
-        let mut new_rewards = vec![];
-        let k_armed_bandit = KArmedBandit::new(k);
-        let selector = UCBSelector::new(confidence);
-        let q_store = MemoryStore::new();
-        let state_value_store = MemoryStore::new();
-        let store_action_count = MemoryStore::new();
-        let mut agent = AgentQ::new(
-            k_armed_bandit,
-            selector,
-            q_store,
-            state_value_store,
-            store_action_count
-        );
-        for _ in 0..num_steps {
-            let action = agent.select_action();
-            let reward = agent.take_action(action);
-            agent.update_estimate(state.clone(), action, reward, true);
-            new_rewards.push(reward);
-        }
-        all_rewards.push(new_rewards);
-    }
-    // now average the rewards across all runs
-    let mut average_rewards: Vec<f64> = vec![];
-    for i in 0..num_steps {
-        let mut total_reward = 0.0;
-        for r in 0..independent_runs {
-            total_reward += all_rewards[r][i];
-        }
-        average_rewards.push(total_reward / (independent_runs as f64));
-    }
+        let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+        AgentQ::new(
+            KArmedBandit::with_seed(k, seeds.child(0).seed()),
+            UCBSelector::new(confidence),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            MemoryStore::new()
+        )
+    });
     println!("Completed for confidence: {}", confidence);
-    average_rewards
+    println!(
+        "Cumulative regret after {} steps: {:.1}",
+        num_steps,
+        report.cumulative_regrets().last().unwrap()
+    );
+    report
 }
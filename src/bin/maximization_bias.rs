@@ -0,0 +1,140 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, RED, WHITE },
+};
+use rl_examples::{
+    agents::{ agent::Agent, double_q_learning::AgentDoubleQLearning, q_learning::AgentQLearning },
+    environments::maximization_bias::MaximizationBias,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// both agents see the same rewards and exploration seeds
+const EXPERIMENT_SEED: u64 = 0;
+
+const INDEPENDENT_RUNS: usize = 10000;
+const NUM_EPISODES: usize = 300;
+
+// Q-learning vs Double Q-learning on the MDP of Sutton & Barto Example 6.7 (Figure 6.5).
+// Greedy ties go to the last action, right, so unlike the book both agents start out moving right
+fn main() {
+    let epsilon = 0.1;
+    let step_size = 0.1;
+    let discount = 1.0;
+    let num_b_actions = 10;
+
+    println!("Running Q-learning");
+    let q_learning_lefts = left_action_rates(|seeds| {
+        AgentQLearning::new(
+            MaximizationBias::with_seed(num_b_actions, seeds.child(0).seed()),
+            EpsilonGreedySelector::with_seed(epsilon, seeds.child(1).seed()),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            step_size,
+            discount
+        )
+    });
+
+    println!("Running Double Q-learning");
+    let double_q_learning_lefts = left_action_rates(|seeds| {
+        AgentDoubleQLearning::with_seed(
+            MaximizationBias::with_seed(num_b_actions, seeds.child(0).seed()),
+            EpsilonGreedySelector::with_seed(epsilon, seeds.child(1).seed()),
+            [MemoryStore::new(), MemoryStore::new()],
+            MemoryStore::new(),
+            step_size,
+            discount,
+            seeds.child(2).seed()
+        )
+    });
+
+    println!(
+        "Left actions from A in the last episode - Q-learning: {:.1}%, Double Q-learning: {:.1}%",
+        q_learning_lefts.last().unwrap(),
+        double_q_learning_lefts.last().unwrap()
+    );
+
+    let plot_location = "plots/maximization_bias.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Q-learning vs Double Q-learning", ("sans-serif", 30))
+        .build_cartesian_2d(1..NUM_EPISODES, 0.0..100.0)
+        .unwrap();
+
+    ctx.configure_mesh().x_desc("Episodes").y_desc("% left actions from A").draw().unwrap();
+
+    ctx.draw_series(
+        LineSeries::new(
+            q_learning_lefts
+                .iter()
+                .enumerate()
+                .map(|(i, rate)| (i + 1, *rate)),
+            &RED
+        )
+    )
+        .unwrap()
+        .label("Q-learning")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    ctx.draw_series(
+        LineSeries::new(
+            double_q_learning_lefts
+                .iter()
+                .enumerate()
+                .map(|(i, rate)| (i + 1, *rate)),
+            &BLUE
+        )
+    )
+        .unwrap()
+        .label("Double Q-learning")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    // an ε-greedy policy that prefers right still explores left half of the time it explores
+    let optimal = 100.0 * epsilon / 2.0;
+    ctx.draw_series(LineSeries::new(vec![(1, optimal), (NUM_EPISODES, optimal)], &BLACK))
+        .unwrap()
+        .label("Optimal")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLACK));
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+///
+/// Percentage of runs that moved left from A in every episode
+///
+/// # Arguments
+///
+/// * `create_agent` - F - called with the seeds of a run to create the agent for that run
+fn left_action_rates<A: Agent, F: Fn(SeedStream) -> A>(create_agent: F) -> Vec<f64> {
+    // a single episode at a time so the first action of each episode can be inspected
+    let trainer = Trainer::new(TrainingBudget::Episodes(1), 1);
+    let mut lefts = vec![0.0; NUM_EPISODES];
+    for r in 0..INDEPENDENT_RUNS {
+        let mut agent = create_agent(SeedStream::new(EXPERIMENT_SEED).child(r as u64));
+        for left in lefts.iter_mut() {
+            // every episode starts in A, where moving left is the only sub-optimal action
+            let record = trainer.train(&mut agent);
+            if !record.optimal_actions[0] {
+                *left += 100.0 / (INDEPENDENT_RUNS as f64);
+            }
+        }
+    }
+    lefts
+}
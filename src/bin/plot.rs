@@ -1,5 +1,6 @@
 use rand::{ thread_rng, Rng };
 use plotters::prelude::*;
+use std::f64::consts::PI;
 
 fn main() {
     println!("Hello, world!");
@@ -13,7 +14,7 @@ fn main() {
     root_drawing_area.fill(&WHITE).unwrap();
 
     let mut chart = ChartBuilder::on(&root_drawing_area)
-        .build_cartesian_2d(-3.14..3.14, -1.2..1.2)
+        .build_cartesian_2d(-PI..PI, -1.2..1.2)
         .unwrap();
 
     chart
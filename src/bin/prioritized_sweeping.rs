@@ -0,0 +1,177 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    coord::combinators::IntoLogRange,
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, BLACK, BLUE, RED, WHITE },
+};
+use rl_examples::{
+    agents::{
+        agent::Agent,
+        dyna_q::{ AgentDynaQ, DynaConfig },
+        prioritized_sweeping::{ AgentPrioritizedSweeping, PrioritizedSweepingConfig },
+    },
+    environment::Environment,
+    environments::gridworld::{ GridAction, GridPosition, Gridworld },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// both agents see the same exploration seeds on every maze
+const EXPERIMENT_SEED: u64 = 0;
+
+const INDEPENDENT_RUNS: usize = 5;
+const PLANNING_STEPS: usize = 5;
+const STEP_SIZE: f64 = 0.5;
+const DISCOUNT: f64 = 0.95;
+const EPSILON: f64 = 0.1;
+// the greedy path may be this much longer than the shortest one, as the corners of the blocks
+// make the exact optimum hard to single out
+const PATH_TOLERANCE: f64 = 1.2;
+
+// Updates needed to find an optimal path with prioritized sweeping and Dyna-Q on ever finer
+// versions of the Dyna maze, in the style of Sutton & Barto Example 8.4
+fn main() {
+    let mut dyna_q_updates = vec![];
+    let mut prioritized_sweeping_updates = vec![];
+    for factor in 1..=5 {
+        let maze = Gridworld::dyna_maze().scale(factor);
+        let num_states = maze.get_number_of_possible_states() as f64;
+        let shortest_path_length = maze.shortest_path_length().unwrap() as f64;
+        let max_path_length = (shortest_path_length * PATH_TOLERANCE) as usize;
+
+        let mut total_dyna_q_updates = 0.0;
+        let mut total_prioritized_sweeping_updates = 0.0;
+        for r in 0..INDEPENDENT_RUNS {
+            let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+            let mut dyna_q = AgentDynaQ::with_seed(
+                maze.clone(),
+                EpsilonGreedySelector::with_seed(EPSILON, seeds.child(0).seed())
+                    .with_random_ties(),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                DynaConfig {
+                    planning_steps: PLANNING_STEPS,
+                    step_size: STEP_SIZE,
+                    discount: DISCOUNT,
+                    exploration_bonus: None,
+                },
+                seeds.child(1).seed()
+            );
+            train_until_optimal(&mut dyna_q, &maze, max_path_length, |agent, state, action| {
+                agent.get_q_estimate(state, action)
+            });
+            total_dyna_q_updates += dyna_q.get_total_updates() as f64;
+
+            let mut prioritized_sweeping = AgentPrioritizedSweeping::new(
+                maze.clone(),
+                EpsilonGreedySelector::with_seed(EPSILON, seeds.child(0).seed())
+                    .with_random_ties(),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                PrioritizedSweepingConfig {
+                    planning_steps: PLANNING_STEPS,
+                    step_size: STEP_SIZE,
+                    discount: DISCOUNT,
+                    threshold: 0.0001,
+                }
+            );
+            train_until_optimal(
+                &mut prioritized_sweeping,
+                &maze,
+                max_path_length,
+                |agent, state, action| agent.get_q_estimate(state, action)
+            );
+            total_prioritized_sweeping_updates += prioritized_sweeping.get_total_updates() as f64;
+        }
+        let runs = INDEPENDENT_RUNS as f64;
+        println!(
+            "{} states - updates until optimal, Dyna-Q: {:.0}, prioritized sweeping: {:.0}",
+            num_states,
+            total_dyna_q_updates / runs,
+            total_prioritized_sweeping_updates / runs
+        );
+        dyna_q_updates.push((num_states, total_dyna_q_updates / runs));
+        prioritized_sweeping_updates.push((num_states, total_prioritized_sweeping_updates / runs));
+    }
+
+    let plot_location = "plots/prioritized_sweeping.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let max_states = dyna_q_updates.last().unwrap().0;
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 60)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Prioritized Sweeping vs Dyna-Q", ("sans-serif", 30))
+        .build_cartesian_2d(0.0..max_states, (100.0..1000000.0).log_scale())
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("Gridworld size (#states)")
+        .y_desc("Updates until optimal solution")
+        .y_label_formatter(&|updates| format!("{:.0e}", updates))
+        .draw()
+        .unwrap();
+
+    ctx.draw_series(LineSeries::new(dyna_q_updates, &BLUE))
+        .unwrap()
+        .label("Dyna-Q")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE));
+
+    ctx.draw_series(LineSeries::new(prioritized_sweeping_updates, &RED))
+        .unwrap()
+        .label("Prioritized sweeping")
+        .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], RED));
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+///
+/// Train an agent one episode at a time until following its greedy policy from the start
+/// reaches the goal within `max_path_length` steps
+///
+/// # Arguments
+///
+/// * `agent` - &mut A - agent to train
+/// * `maze` - &Gridworld - maze the agent is trained on
+/// * `max_path_length` - usize - longest path that counts as optimal
+/// * `get_q_estimate` - Q - action value estimate of the agent
+fn train_until_optimal<A: Agent, Q: Fn(&A, GridPosition, GridAction) -> f64>(
+    agent: &mut A,
+    maze: &Gridworld,
+    max_path_length: usize,
+    get_q_estimate: Q
+) {
+    let trainer = Trainer::new(TrainingBudget::Episodes(1), 1);
+    loop {
+        trainer.train(agent);
+        let mut maze = maze.clone();
+        let mut path_length = 0;
+        while !maze.is_terminal() && path_length < max_path_length {
+            let state = maze.get_state();
+            let action = maze
+                .get_actions()
+                .into_iter()
+                .max_by(|a, b| {
+                    get_q_estimate(agent, state, *a).total_cmp(&get_q_estimate(agent, state, *b))
+                })
+                .unwrap();
+            maze.step(action);
+            path_length += 1;
+        }
+        if maze.is_terminal() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, Palette, Palette99, BLACK, WHITE },
+};
+use rl_examples::{
+    agents::n_step_td::AgentNStepTd,
+    environments::random_walk::RandomWalk,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::DenseStore,
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// every n and step size sees the same random walks
+const EXPERIMENT_SEED: u64 = 0;
+
+// n-step TD on the 19 state random walk, reproducing Sutton & Barto Figure 7.2
+fn main() {
+    let independent_runs = 100;
+    let num_episodes = 10;
+    let ns = [1, 2, 4, 8, 16, 32, 64, 128, 256, 512];
+    let step_sizes: Vec<f64> = (0..=20).map(|i| (i as f64) * 0.05).collect();
+    // a single episode at a time so the error can be measured after each one
+    let trainer = Trainer::new(TrainingBudget::Episodes(1), 1);
+
+    let mut errors_by_n = vec![];
+    for n in ns {
+        let mut errors = vec![];
+        for step_size in step_sizes.iter() {
+            let mut total_error = 0.0;
+            for r in 0..independent_runs {
+                let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+                let random_walk = RandomWalk::nineteen_state();
+                // the random policy moves left or right with equal probability
+                let mut agent = AgentNStepTd::new(
+                    random_walk.clone(),
+                    EpsilonGreedySelector::with_seed(1.0, seeds.child(0).seed()),
+                    DenseStore::from_environment(&random_walk),
+                    DenseStore::from_environment(&random_walk),
+                    n,
+                    *step_size,
+                    1.0
+                );
+                for _ in 0..num_episodes {
+                    trainer.train(&mut agent);
+                    total_error += random_walk.rms_error(|state| {
+                        agent.get_state_value_estimate(state)
+                    });
+                }
+            }
+            let average_error = total_error / ((independent_runs * num_episodes) as f64);
+            errors.push((*step_size, average_error));
+        }
+        let best = errors
+            .iter()
+            .fold((0.0, f64::INFINITY), |best, error| if error.1 < best.1 { *error } else { best });
+        println!("n = {:>3}: lowest RMS error {:.4} with step size {:.2}", n, best.1, best.0);
+        errors_by_n.push((n, errors));
+    }
+
+    let plot_location = "plots/random_walk_n_step.png";
+    let root_area = BitMapBackend::new(plot_location, (600, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Right, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("n-step TD on the Random Walk", ("sans-serif", 30))
+        .build_cartesian_2d(0.0..1.0, 0.25..0.55)
+        .unwrap();
+
+    ctx.configure_mesh()
+        .x_desc("Step size")
+        .y_desc("Average RMS error")
+        .draw()
+        .unwrap();
+
+    for (i, (n, errors)) in errors_by_n.into_iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        // errors above the top of the plot are left out, as in the book
+        let errors: Vec<(f64, f64)> = errors
+            .into_iter()
+            .take_while(|(_, error)| *error <= 0.55)
+            .collect();
+        ctx.draw_series(LineSeries::new(errors, &color))
+            .unwrap()
+            .label(format!("n = {}", n))
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
@@ -0,0 +1,162 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, Palette, Palette99, BLACK, WHITE },
+};
+use rl_examples::{
+    agents::{
+        agent::Agent,
+        td_lambda::{ AgentTdLambda, AgentTrueOnlineTd, TdLambdaConfig, TraceType },
+    },
+    environments::random_walk::RandomWalk,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::DenseStore,
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// every method, λ and step size sees the same random walks
+const EXPERIMENT_SEED: u64 = 0;
+
+const INDEPENDENT_RUNS: usize = 100;
+const NUM_EPISODES: usize = 10;
+const LAMBDAS: [f64; 8] = [0.0, 0.4, 0.8, 0.9, 0.95, 0.975, 0.99, 1.0];
+
+// λ sweep of TD(λ) with each trace type and of true online TD(λ) on the 19 state random walk,
+// in the style of Sutton & Barto Figures 12.3 and 12.8
+fn main() {
+    let step_sizes: Vec<f64> = (0..=20).map(|i| (i as f64) * 0.05).collect();
+    let methods = ["Accumulating traces", "Replacing traces", "Dutch traces", "True online TD(λ)"];
+
+    let plot_location = "plots/random_walk_td_lambda.png";
+    let root_area = BitMapBackend::new(plot_location, (1000, 800)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+    let panels = root_area.split_evenly((2, 2));
+
+    for (method, panel) in methods.iter().zip(panels.iter()) {
+        println!("{}", method);
+        let mut ctx = ChartBuilder::on(panel)
+            .set_label_area_size(LabelAreaPosition::Left, 40)
+            .set_label_area_size(LabelAreaPosition::Bottom, 40)
+            .caption(method, ("sans-serif", 24))
+            .margin(10)
+            .build_cartesian_2d(0.0..1.0, 0.25..0.55)
+            .unwrap();
+        ctx.configure_mesh().x_desc("Step size").y_desc("Average RMS error").draw().unwrap();
+
+        for (i, lambda) in LAMBDAS.iter().enumerate() {
+            let errors: Vec<(f64, f64)> = step_sizes
+                .iter()
+                .map(|step_size| {
+                    let error = match *method {
+                        "Accumulating traces" =>
+                            td_lambda_error(TraceType::Accumulating, *lambda, *step_size),
+                        "Replacing traces" =>
+                            td_lambda_error(TraceType::Replacing, *lambda, *step_size),
+                        "Dutch traces" => td_lambda_error(TraceType::Dutch, *lambda, *step_size),
+                        _ => true_online_error(*lambda, *step_size),
+                    };
+                    (*step_size, error)
+                })
+                .collect();
+            let best = errors
+                .iter()
+                .fold((0.0, f64::INFINITY), |best, error| if error.1 < best.1 { *error } else { best });
+            println!(
+                "  λ = {:<5}: lowest RMS error {:.4} with step size {:.2}",
+                lambda,
+                best.1,
+                best.0
+            );
+            let color = Palette99::pick(i).to_rgba();
+            // errors above the top of the plot are left out, as in the book
+            let errors: Vec<(f64, f64)> = errors
+                .into_iter()
+                .take_while(|(_, error)| *error <= 0.55)
+                .collect();
+            ctx.draw_series(LineSeries::new(errors, &color))
+                .unwrap()
+                .label(format!("λ = {}", lambda))
+                .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+        }
+
+        ctx.configure_series_labels()
+            .border_style(BLACK)
+            .background_style(WHITE.mix(0.8))
+            .draw()
+            .unwrap();
+    }
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+/// Average RMS error of TD(λ) over the first episodes of every run
+fn td_lambda_error(trace_type: TraceType, lambda: f64, step_size: f64) -> f64 {
+    average_error(
+        |seed| {
+            let random_walk = RandomWalk::nineteen_state();
+            AgentTdLambda::new(
+                random_walk.clone(),
+                EpsilonGreedySelector::with_seed(1.0, seed),
+                DenseStore::from_environment(&random_walk),
+                DenseStore::from_environment(&random_walk),
+                TdLambdaConfig {
+                    trace_type,
+                    lambda,
+                    step_size,
+                    discount: 1.0,
+                }
+            )
+        },
+        |agent, state| agent.get_state_value_estimate(state)
+    )
+}
+
+/// Average RMS error of true online TD(λ) over the first episodes of every run
+fn true_online_error(lambda: f64, step_size: f64) -> f64 {
+    average_error(
+        |seed| {
+            let random_walk = RandomWalk::nineteen_state();
+            AgentTrueOnlineTd::new(
+                random_walk.clone(),
+                EpsilonGreedySelector::with_seed(1.0, seed),
+                DenseStore::from_environment(&random_walk),
+                DenseStore::from_environment(&random_walk),
+                lambda,
+                step_size,
+                1.0
+            )
+        },
+        |agent, state| agent.get_state_value_estimate(state)
+    )
+}
+
+///
+/// Root mean square error of the state value estimates after each episode, averaged over
+/// episodes and runs
+///
+/// # Arguments
+///
+/// * `create_agent` - F - called with the seed of the random policy of a run
+/// * `get_state_value_estimate` - V - estimated value of a state
+fn average_error<A: Agent, F: Fn(u64) -> A, V: Fn(&A, usize) -> f64>(
+    create_agent: F,
+    get_state_value_estimate: V
+) -> f64 {
+    let random_walk = RandomWalk::nineteen_state();
+    // a single episode at a time so the error can be measured after each one
+    let trainer = Trainer::new(TrainingBudget::Episodes(1), 1);
+    let mut total_error = 0.0;
+    for r in 0..INDEPENDENT_RUNS {
+        let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+        let mut agent = create_agent(seeds.child(0).seed());
+        for _ in 0..NUM_EPISODES {
+            trainer.train(&mut agent);
+            total_error += random_walk.rms_error(|state| get_state_value_estimate(&agent, state));
+        }
+    }
+    total_error / ((INDEPENDENT_RUNS * NUM_EPISODES) as f64)
+}
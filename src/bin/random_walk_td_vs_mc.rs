@@ -0,0 +1,178 @@
+use plotters::{
+    backend::BitMapBackend,
+    chart::{ ChartBuilder, LabelAreaPosition },
+    drawing::IntoDrawingArea,
+    element::PathElement,
+    series::LineSeries,
+    style::{ Color, Palette, Palette99, BLACK, WHITE },
+};
+use rl_examples::{
+    agents::n_step_td::AgentNStepTd,
+    environment::Environment,
+    environments::random_walk::{ RandomWalk, RandomWalkAction },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ DenseStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// every method and step size sees the same random walks
+const EXPERIMENT_SEED: u64 = 0;
+
+// value every state is estimated at before the first episode
+const INITIAL_VALUE: f64 = 0.5;
+
+type RandomWalkAgent = AgentNStepTd<
+    RandomWalk,
+    EpsilonGreedySelector,
+    DenseStore<usize, RandomWalkAction>
+>;
+
+// TD(0) against constant-α Monte Carlo on the 5 state random walk, reproducing
+// Sutton & Barto Example 6.2
+fn main() {
+    let independent_runs = 100;
+    let num_episodes = 100;
+    // a single episode at a time so the estimates can be measured after each one
+    let trainer = Trainer::new(TrainingBudget::Episodes(1), 1);
+    let random_walk = RandomWalk::five_state();
+
+    // estimates of a single TD(0) run after some of its episodes
+    let snapshot_episodes = [0, 1, 10, 100];
+    let mut agent = create_agent(1, 0.1, SeedStream::new(EXPERIMENT_SEED).seed());
+    let mut snapshots = vec![];
+    for episode in 0..=num_episodes {
+        if snapshot_episodes.contains(&episode) {
+            let estimates: Vec<f64> = random_walk
+                .all_possible_states()
+                .into_iter()
+                .map(|state| agent.get_state_value_estimate(state))
+                .collect();
+            snapshots.push((format!("{} episodes", episode), estimates));
+        }
+        trainer.train(&mut agent);
+    }
+    snapshots.push(("True values".to_string(), random_walk.true_state_values()));
+
+    // RMS error averaged over runs, TD(0) is n-step TD with n = 1 and Monte Carlo never bootstraps
+    let methods = [
+        ("TD α = 0.15", 1, 0.15),
+        ("TD α = 0.1", 1, 0.1),
+        ("TD α = 0.05", 1, 0.05),
+        ("MC α = 0.01", usize::MAX, 0.01),
+        ("MC α = 0.02", usize::MAX, 0.02),
+        ("MC α = 0.03", usize::MAX, 0.03),
+        ("MC α = 0.04", usize::MAX, 0.04),
+    ];
+    let mut errors_by_method = vec![];
+    for (label, n, step_size) in methods {
+        let mut errors = vec![0.0; num_episodes + 1];
+        for r in 0..independent_runs {
+            let seeds = SeedStream::new(EXPERIMENT_SEED).child(r as u64);
+            let mut agent = create_agent(n, step_size, seeds.child(0).seed());
+            for error in errors.iter_mut() {
+                let rms_error = random_walk.rms_error(|state| {
+                    agent.get_state_value_estimate(state)
+                });
+                *error += rms_error / (independent_runs as f64);
+                trainer.train(&mut agent);
+            }
+        }
+        println!("{}: RMS error after {} episodes {:.4}", label, num_episodes, errors.last().unwrap());
+        errors_by_method.push((label, errors));
+    }
+
+    let plot_location = "plots/random_walk_td_vs_mc.png";
+    let root_area = BitMapBackend::new(plot_location, (1200, 400)).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+    let panels = root_area.split_evenly((1, 2));
+
+    let mut ctx = ChartBuilder::on(&panels[0])
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Estimated Value", ("sans-serif", 30))
+        .margin(10)
+        .build_cartesian_2d(1..5usize, 0.0..1.0)
+        .unwrap();
+    ctx.configure_mesh().x_desc("State").x_labels(5).draw().unwrap();
+    for (i, (label, estimates)) in snapshots.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        ctx.draw_series(
+            LineSeries::new(
+                estimates
+                    .iter()
+                    .enumerate()
+                    .map(|(state, v)| (state + 1, *v)),
+                &color
+            )
+        )
+            .unwrap()
+            .label(label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    let mut ctx = ChartBuilder::on(&panels[1])
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption("Empirical RMS Error", ("sans-serif", 30))
+        .margin(10)
+        .build_cartesian_2d(0..num_episodes, 0.0..0.25)
+        .unwrap();
+    ctx.configure_mesh()
+        .x_desc("Episodes")
+        .y_desc("RMS error, averaged over states")
+        .draw()
+        .unwrap();
+    for (i, (label, errors)) in errors_by_method.iter().enumerate() {
+        let color = Palette99::pick(i).to_rgba();
+        ctx.draw_series(
+            LineSeries::new(
+                errors
+                    .iter()
+                    .enumerate()
+                    .map(|(episode, error)| (episode, *error)),
+                &color
+            )
+        )
+            .unwrap()
+            .label(*label)
+            .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+
+    println!("Plot saved at: {}", plot_location);
+}
+
+///
+/// Create an agent following the random policy, with every state estimated at `INITIAL_VALUE`
+///
+/// # Arguments
+///
+/// * `n` - usize - number of rewards before bootstrapping
+/// * `step_size` - f64 - step size (alpha) of the update
+/// * `seed` - u64 - seed of the random policy
+fn create_agent(n: usize, step_size: f64, seed: u64) -> RandomWalkAgent {
+    let random_walk = RandomWalk::five_state();
+    let mut state_value_store = DenseStore::from_environment(&random_walk);
+    for state in random_walk.all_possible_states() {
+        state_value_store.store_float((state, None), INITIAL_VALUE);
+    }
+    AgentNStepTd::new(
+        random_walk.clone(),
+        EpsilonGreedySelector::with_seed(1.0, seed),
+        state_value_store,
+        DenseStore::from_environment(&random_walk),
+        n,
+        step_size,
+        1.0
+    )
+}
@@ -0,0 +1,95 @@
+use rand::{ rngs::StdRng, seq::SliceRandom, SeedableRng };
+use rl_examples::{
+    agents::uct::UctPlanner,
+    environment::Environment,
+    environments::tic_tac_toe::{ Mark, TicTacToe },
+    seed::SeedStream,
+};
+
+const EXPERIMENT_SEED: u64 = 0;
+
+fn main() {
+    let num_games = 100;
+    let simulations = 1000;
+    let seeds = SeedStream::new(EXPERIMENT_SEED);
+    let mut x_planner = UctPlanner::with_seed(
+        simulations,
+        (2.0f64).sqrt(),
+        1.0,
+        9,
+        seeds.child(0).seed()
+    );
+    let mut o_planner = UctPlanner::with_seed(
+        simulations,
+        (2.0f64).sqrt(),
+        1.0,
+        9,
+        seeds.child(1).seed()
+    );
+    let mut rng = StdRng::seed_from_u64(seeds.child(2).seed());
+
+    println!("UCT (X) against random (O)");
+    let results = play_games(
+        num_games,
+        &mut (|game: &TicTacToe| x_planner.search(game)),
+        &mut (|game: &TicTacToe| random_move(game, &mut rng))
+    );
+    print_results(&results);
+
+    println!("Random (X) against UCT (O)");
+    let results = play_games(
+        num_games,
+        &mut (|game: &TicTacToe| random_move(game, &mut rng)),
+        &mut (|game: &TicTacToe| o_planner.search(game))
+    );
+    print_results(&results);
+
+    println!("UCT (X) against UCT (O)");
+    let results = play_games(
+        num_games,
+        &mut (|game: &TicTacToe| x_planner.search(game)),
+        &mut (|game: &TicTacToe| o_planner.search(game))
+    );
+    print_results(&results);
+}
+
+fn random_move(game: &TicTacToe, rng: &mut StdRng) -> usize {
+    *game.get_actions().choose(rng).unwrap()
+}
+
+/// Play games between two players and return the winner of every game
+fn play_games<X: FnMut(&TicTacToe) -> usize, O: FnMut(&TicTacToe) -> usize>(
+    num_games: usize,
+    x_player: &mut X,
+    o_player: &mut O
+) -> Vec<Option<Mark>> {
+    let mut winners = vec![];
+    let mut game = TicTacToe::new();
+    for _ in 0..num_games {
+        game.reset();
+        while !game.is_terminal() {
+            let action = if game.get_current_player() == 0 {
+                x_player(&game)
+            } else {
+                o_player(&game)
+            };
+            game.step(action);
+        }
+        winners.push(game.get_winner());
+    }
+    println!("Final board of the last game:\n{}", game);
+    winners
+}
+
+fn print_results(winners: &[Option<Mark>]) {
+    let x_wins = winners
+        .iter()
+        .filter(|winner| **winner == Some(Mark::X))
+        .count();
+    let o_wins = winners
+        .iter()
+        .filter(|winner| **winner == Some(Mark::O))
+        .count();
+    let draws = winners.len() - x_wins - o_wins;
+    println!("X wins: {}, O wins: {}, Draws: {}\n", x_wins, o_wins, draws);
+}
@@ -0,0 +1,301 @@
+use rustc_hash::FxHashMap;
+
+use crate::{ environment::Model, store::StateActionStore };
+
+/// Order in which the states of a sweep are backed up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepMode {
+    /// every backup of a sweep reads the values of the previous sweep
+    Synchronous,
+    /// backups read values updated earlier in the same sweep, which usually converges faster
+    InPlace,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct DpConfig {
+    /// discount factor (gamma) of future rewards
+    pub discount: f64,
+    /// sweeps stop once no state value changes by more than this
+    pub threshold: f64,
+    pub sweep_mode: SweepMode,
+    /// number of sweeps after which a single evaluation stops, needed for undiscounted
+    /// policies that never end an episode
+    pub max_sweeps: Option<usize>,
+}
+
+impl Default for DpConfig {
+    fn default() -> Self {
+        DpConfig {
+            discount: 1.0,
+            threshold: 1e-9,
+            sweep_mode: SweepMode::InPlace,
+            max_sweeps: None,
+        }
+    }
+}
+
+/// Deterministic policy mapping every non-terminal state to an action
+pub type Policy<S, A> = FxHashMap<S, A>;
+
+/// Values and greedy policy found by policy iteration or value iteration
+pub struct DpSolution<S, A, V> {
+    /// state values, stored under the state with no action
+    pub values: V,
+    pub policy: Policy<S, A>,
+    /// number of sweeps over the state space
+    pub sweeps: usize,
+    /// number of policy improvement steps
+    pub improvements: usize,
+}
+
+///
+/// Expected return of taking an action in a state and following the values afterwards
+///
+/// # Arguments
+///
+/// * `model` - &M - environment model
+/// * `values` - &V - state value estimates
+/// * `state` - &M::State - state the action is taken in
+/// * `action` - &M::Action - action to evaluate
+/// * `discount` - f64 - discount factor of future rewards
+pub fn action_value<M: Model, V: StateActionStore<M::State, M::Action>>(
+    model: &M,
+    values: &V,
+    state: &M::State,
+    action: &M::Action,
+    discount: f64
+) -> f64 {
+    model
+        .transitions(state, action)
+        .into_iter()
+        .map(|transition| {
+            let next_value = if transition.is_terminal {
+                0.0
+            } else {
+                values.get_float(&values.generate_id(transition.next_state, None))
+            };
+            transition.probability * (transition.reward + discount * next_value)
+        })
+        .sum()
+}
+
+///
+/// Policy that picks every available action with equal probability
+///
+/// # Arguments
+///
+/// * `model` - &M - environment model
+pub fn equiprobable_policy<M: Model>(
+    model: &M
+) -> impl Fn(&M::State) -> Vec<(M::Action, f64)> + '_ {
+    move |state| {
+        let actions = model.actions_in(state);
+        let probability = 1.0 / (actions.len() as f64);
+        actions
+            .into_iter()
+            .map(|action| (action, probability))
+            .collect()
+    }
+}
+
+///
+/// Iterative policy evaluation of a stochastic policy
+/// Returns the state values and the number of sweeps taken
+///
+/// # Arguments
+///
+/// * `model` - &M - environment model
+/// * `policy` - F - probability of taking each action in a state
+/// * `config` - DpConfig - discount, convergence threshold and sweep mode
+pub fn evaluate_policy<
+    M: Model,
+    V: StateActionStore<M::State, M::Action>,
+    F: Fn(&M::State) -> Vec<(M::Action, f64)>
+>(model: &M, policy: F, config: DpConfig) -> (V, usize) {
+    let mut values = V::new();
+    let sweeps = evaluate_policy_into(model, &mut values, &policy, config);
+    (values, sweeps)
+}
+
+///
+/// Policy iteration. The first evaluation is of the equiprobable policy, so every policy that
+/// follows improves on one that ends its episodes. The policy only changes in a state when
+/// another action is better by more than the threshold, which stops equally good actions
+/// from alternating forever.
+///
+/// # Arguments
+///
+/// * `model` - &M - environment model
+/// * `config` - DpConfig - discount, convergence threshold and sweep mode
+pub fn policy_iteration<M: Model, V: StateActionStore<M::State, M::Action>>(
+    model: &M,
+    config: DpConfig
+) -> DpSolution<M::State, M::Action, V> {
+    let mut values = V::new();
+    let mut sweeps = evaluate_policy_into(model, &mut values, &equiprobable_policy(model), config);
+    let mut policy: Policy<M::State, M::Action> = FxHashMap::default();
+    let mut improvements = 0;
+    loop {
+        improvements += 1;
+        let mut is_stable = true;
+        for state in model.all_possible_states() {
+            let actions = model.actions_in(&state);
+            if actions.is_empty() {
+                continue;
+            }
+            let greedy_action = greedy_action(model, &values, &state, &actions, config.discount);
+            let is_improvement = match policy.get(&state) {
+                Some(action) =>
+                    action_value(model, &values, &state, &greedy_action, config.discount) >
+                        action_value(model, &values, &state, action, config.discount) +
+                            config.threshold,
+                None => true,
+            };
+            if is_improvement {
+                policy.insert(state, greedy_action);
+                is_stable = false;
+            }
+        }
+        if is_stable {
+            break;
+        }
+        let deterministic_policy = |state: &M::State| {
+            policy
+                .get(state)
+                .map(|action| vec![(action.clone(), 1.0)])
+                .unwrap_or_default()
+        };
+        sweeps += evaluate_policy_into(model, &mut values, &deterministic_policy, config);
+    }
+    DpSolution {
+        values,
+        policy,
+        sweeps,
+        improvements,
+    }
+}
+
+///
+/// Value iteration, backing up the best action of every state until the values converge
+/// and then taking the policy that is greedy with respect to them
+///
+/// # Arguments
+///
+/// * `model` - &M - environment model
+/// * `config` - DpConfig - discount, convergence threshold and sweep mode
+pub fn value_iteration<M: Model, V: StateActionStore<M::State, M::Action>>(
+    model: &M,
+    config: DpConfig
+) -> DpSolution<M::State, M::Action, V> {
+    let mut values = V::new();
+    let sweeps = sweep_until_converged(model, &mut values, config, |values, state| {
+        model
+            .actions_in(state)
+            .iter()
+            .map(|action| action_value(model, values, state, action, config.discount))
+            .fold(f64::NEG_INFINITY, f64::max)
+    });
+    let mut policy: Policy<M::State, M::Action> = FxHashMap::default();
+    for state in model.all_possible_states() {
+        let actions = model.actions_in(&state);
+        if !actions.is_empty() {
+            let action = greedy_action(model, &values, &state, &actions, config.discount);
+            policy.insert(state, action);
+        }
+    }
+    DpSolution {
+        values,
+        policy,
+        sweeps,
+        improvements: 1,
+    }
+}
+
+/// Action with the highest value in a state, ties go to the last action as with the greedy selectors
+fn greedy_action<M: Model, V: StateActionStore<M::State, M::Action>>(
+    model: &M,
+    values: &V,
+    state: &M::State,
+    actions: &[M::Action],
+    discount: f64
+) -> M::Action {
+    let mut max = f64::NEG_INFINITY;
+    let mut max_action = &actions[0];
+    for action in actions {
+        let value = action_value(model, values, state, action, discount);
+        if value >= max {
+            max = value;
+            max_action = action;
+        }
+    }
+    max_action.clone()
+}
+
+/// Evaluate a policy starting from the given values, returning the number of sweeps taken
+fn evaluate_policy_into<
+    M: Model,
+    V: StateActionStore<M::State, M::Action>,
+    F: Fn(&M::State) -> Vec<(M::Action, f64)>
+>(model: &M, values: &mut V, policy: &F, config: DpConfig) -> usize {
+    sweep_until_converged(model, values, config, |values, state| {
+        policy(state)
+            .iter()
+            .map(|(action, probability)| {
+                probability * action_value(model, values, state, action, config.discount)
+            })
+            .sum()
+    })
+}
+
+///
+/// Sweep the state space with a backup until the largest change of a sweep is below the threshold
+/// Returns the number of sweeps taken
+///
+/// # Arguments
+///
+/// * `model` - &M - environment model whose states are swept
+/// * `values` - &mut V - state values to update
+/// * `config` - DpConfig - convergence threshold, sweep mode and sweep limit
+/// * `backup` - F - new value of a state given the current values
+fn sweep_until_converged<
+    M: Model,
+    V: StateActionStore<M::State, M::Action>,
+    F: Fn(&V, &M::State) -> f64
+>(model: &M, values: &mut V, config: DpConfig, backup: F) -> usize {
+    // terminal states have no actions and keep a value of zero
+    let states: Vec<M::State> = model
+        .all_possible_states()
+        .into_iter()
+        .filter(|state| !model.actions_in(state).is_empty())
+        .collect();
+    let mut sweeps = 0;
+    loop {
+        sweeps += 1;
+        let mut max_change: f64 = 0.0;
+        match config.sweep_mode {
+            SweepMode::InPlace => {
+                for state in states.iter() {
+                    let value = backup(values, state);
+                    let id = values.generate_id(state.clone(), None);
+                    max_change = max_change.max((value - values.get_float(&id)).abs());
+                    values.store_float(id, value);
+                }
+            }
+            SweepMode::Synchronous => {
+                let new_values: Vec<f64> = states
+                    .iter()
+                    .map(|state| backup(values, state))
+                    .collect();
+                for (state, value) in states.iter().zip(new_values) {
+                    let id = values.generate_id(state.clone(), None);
+                    max_change = max_change.max((value - values.get_float(&id)).abs());
+                    values.store_float(id, value);
+                }
+            }
+        }
+        let reached_max_sweeps = config.max_sweeps.is_some_and(|max_sweeps| sweeps >= max_sweeps);
+        if max_change < config.threshold || reached_max_sweeps {
+            return sweeps;
+        }
+    }
+}
@@ -1,15 +1,66 @@
+use crate::agents::agent::{ Action, State };
+
 // TODO: q value and value estimates should be handled by selector
 // TODO: method that returns tuple of possible actions based on state
-// TODO: add trainer module
 
 pub trait Environment {
+    type State: State;
+    type Action: Action;
     fn reset(&mut self);
-    fn step(&mut self, action: usize) -> f64;
-    fn get_state(&self) -> String;
-    fn get_actions(&self) -> Vec<usize>;
+    fn step(&mut self, action: Self::Action) -> f64;
+    fn get_state(&self) -> Self::State;
+    fn get_actions(&self) -> Vec<Self::Action>;
     fn is_terminal(&self) -> bool;
     fn get_number_of_possible_actions(&self) -> usize;
     fn get_number_of_possible_states(&self) -> usize;
     fn get_total_number_of_actions_taken(&self) -> usize;
-    fn all_possible_states(&self) -> Vec<String>;
+    fn all_possible_states(&self) -> Vec<Self::State>;
+    /// Whether the action is optimal in the current state, if the environment knows
+    fn is_optimal_action(&self, _action: &Self::Action) -> Option<bool> {
+        None
+    }
+    /// Expected reward lost by taking the action instead of an optimal one, if the environment knows
+    fn regret(&self, _action: &Self::Action) -> Option<f64> {
+        None
+    }
+    /// Index of the player to act in multi-player games.
+    /// Rewards returned by `step` belong to the player who took the action.
+    fn get_current_player(&self) -> usize {
+        0
+    }
+}
+
+/// Environments that can be copied to simulate ahead, as needed by planners
+pub trait Snapshot: Environment + Sized {
+    /// Copy of the environment in its current state. Randomness the agent cannot observe,
+    /// like the next cards of a deck, is redrawn from `seed` rather than copied.
+    fn snapshot(&self, seed: u64) -> Self;
+}
+
+/// Environments that can start an episode in any of their states, as needed by exploring starts
+pub trait ResetToState: Environment {
+    fn reset_to_state(&mut self, state: &Self::State);
+}
+
+/// One possible outcome of taking an action in a state
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition<S> {
+    pub probability: f64,
+    /// state the environment moves to, its value is ignored when the episode terminates
+    pub next_state: S,
+    pub reward: f64,
+    pub is_terminal: bool,
+}
+
+/// Environments that can enumerate the distribution `p(s', r | s, a)` of their dynamics,
+/// as needed by dynamic programming
+pub trait Model: Environment {
+    /// Actions available in any state, not only the current one. Terminal states have none.
+    fn actions_in(&self, state: &Self::State) -> Vec<Self::Action>;
+    /// Every outcome of taking an action in a state, with probabilities summing to one
+    fn transitions(
+        &self,
+        state: &Self::State,
+        action: &Self::Action
+    ) -> Vec<Transition<Self::State>>;
 }
@@ -1,49 +1,174 @@
-use rand::thread_rng;
-use rand_distr::{ Distribution, Normal };
+use rand::{ rngs::StdRng, Rng, SeedableRng };
+use rand_distr::{ Cauchy, Distribution, Exp, Normal, StudentT };
 
 use crate::environment::Environment;
 
+/// Draws a reward from the given rng
+pub type RewardSampler = Box<dyn Fn(&mut StdRng) -> f64>;
+
+/// Reward distribution of a bandit
 pub enum BanditType {
+    /// unit variance normal rewards around a true value drawn from N(0, 1), as in the
+    /// 10-armed testbed of Sutton & Barto
     Gaussian,
+    Normal {
+        mean: f64,
+        standard_deviation: f64,
+    },
+    /// reward of 1 with the given probability and 0 otherwise, like a click-through
+    Bernoulli {
+        probability: f64,
+    },
+    Uniform {
+        low: f64,
+        high: f64,
+    },
+    Exponential {
+        rate: f64,
+    },
+    /// heavy-tailed rewards, whose mean only exists for more than 1 degree of freedom
+    StudentT {
+        location: f64,
+        scale: f64,
+        degrees_of_freedom: f64,
+    },
+    /// heavy-tailed rewards without a mean, the location is their median
+    Cauchy {
+        location: f64,
+        scale: f64,
+    },
+    /// rewards from a user supplied sampler, whose true value must be given
+    Custom {
+        value: f64,
+        sample: RewardSampler,
+    },
+}
+
+impl BanditType {
+    /// Panic with a clear message when the parameters do not describe a distribution
+    fn validate(&self) {
+        match self {
+            BanditType::Gaussian | BanditType::Custom { .. } => {}
+            BanditType::Normal { standard_deviation, .. } => {
+                assert!(*standard_deviation >= 0.0, "standard deviation must not be negative");
+            }
+            BanditType::Bernoulli { probability } => {
+                assert!((0.0..=1.0).contains(probability), "probability must be between 0 and 1");
+            }
+            BanditType::Uniform { low, high } => {
+                assert!(low.is_finite() && high.is_finite(), "low and high must be finite");
+                assert!(low <= high, "low must not be greater than high");
+            }
+            BanditType::Exponential { rate } => {
+                assert!(*rate > 0.0, "rate must be positive");
+            }
+            BanditType::StudentT { scale, degrees_of_freedom, .. } => {
+                assert!(*scale >= 0.0, "scale must not be negative");
+                assert!(*degrees_of_freedom > 0.0, "degrees of freedom must be positive");
+            }
+            BanditType::Cauchy { scale, .. } => {
+                assert!(*scale > 0.0, "scale must be positive");
+            }
+        }
+    }
+
+    /// True value a bandit of this type starts with, the location for heavy-tailed types
+    fn initial_value<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        match self {
+            // draw true value from a normal distribution with mean 0 and variance 1
+            BanditType::Gaussian => Normal::new(0.0, 1.0).unwrap().sample(rng),
+            BanditType::Normal { mean, .. } => *mean,
+            BanditType::Bernoulli { probability } => *probability,
+            BanditType::Uniform { low, high } => (low + high) / 2.0,
+            BanditType::Exponential { rate } => 1.0 / rate,
+            BanditType::StudentT { location, .. } => *location,
+            BanditType::Cauchy { location, .. } => *location,
+            BanditType::Custom { value, .. } => *value,
+        }
+    }
 }
 
 pub struct Bandit {
     bandit_type: BanditType,
     value: f64,
-    variance: f64,
     name: String,
     num_pulls: usize,
     total_reward: f64,
+    rng: StdRng,
 }
 
 impl Bandit {
     pub fn new(name: String, bandit_type: BanditType) -> Bandit {
-        // draw true value from a normal distribution with mean 0 and variance 1
-        let normal = Normal::new(0.0, 1.0).unwrap();
-        let value = normal.sample(&mut thread_rng());
-        let variance = 1.0;
+        Bandit::with_rng(name, bandit_type, StdRng::from_entropy())
+    }
+
+    ///
+    /// Create a bandit that draws its true value and rewards from the given rng
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - String - name of the bandit
+    /// * `bandit_type` - BanditType - reward distribution
+    /// * `rng` - StdRng - source of randomness for the bandit
+    pub fn with_rng(name: String, bandit_type: BanditType, mut rng: StdRng) -> Bandit {
+        bandit_type.validate();
+        let value = bandit_type.initial_value(&mut rng);
         Bandit {
             bandit_type,
             value,
-            variance,
             name,
             num_pulls: 0,
             total_reward: 0.0,
+            rng,
         }
     }
 
     pub fn pull(&mut self) -> f64 {
         self.num_pulls += 1;
-        match self.bandit_type {
-            BanditType::Gaussian => {
-                let normal = Normal::new(self.value, self.variance.sqrt()).unwrap();
-                let v = normal.sample(&mut rand::thread_rng());
-                self.total_reward += v;
-                v
+        // rewards are the true value plus noise, so a drifting true value moves every type
+        let v = self.value + self.sample_noise();
+        self.total_reward += v;
+        v
+    }
+
+    /// Deviation of a reward from the true value, centered on zero
+    fn sample_noise(&mut self) -> f64 {
+        let rng = &mut self.rng;
+        match &self.bandit_type {
+            BanditType::Gaussian => Normal::new(0.0, 1.0).unwrap().sample(rng),
+            BanditType::Normal { standard_deviation, .. } => {
+                Normal::new(0.0, *standard_deviation).unwrap().sample(rng)
+            }
+            BanditType::Bernoulli { probability } => {
+                let reward = if rng.gen::<f64>() < *probability { 1.0 } else { 0.0 };
+                reward - probability
             }
+            BanditType::Uniform { low, high } => rng.gen_range(*low..=*high) - (low + high) / 2.0,
+            BanditType::Exponential { rate } => Exp::new(*rate).unwrap().sample(rng) - 1.0 / rate,
+            BanditType::StudentT { scale, degrees_of_freedom, .. } => {
+                scale * StudentT::new(*degrees_of_freedom).unwrap().sample(rng)
+            }
+            BanditType::Cauchy { scale, .. } => Cauchy::new(0.0, *scale).unwrap().sample(rng),
+            BanditType::Custom { value, sample } => sample(rng) - value,
         }
     }
 
+    /// Expected reward of a pull, the median for heavy-tailed bandits without a mean
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    ///
+    /// Move the true value by an independent normally distributed step
+    ///
+    /// # Arguments
+    ///
+    /// * `standard_deviation` - f64 - standard deviation of the step
+    fn random_walk(&mut self, standard_deviation: f64) {
+        let normal = Normal::new(0.0, standard_deviation).unwrap();
+        self.value += normal.sample(&mut self.rng);
+    }
+
     pub fn get_number_of_pulls(&self) -> usize {
         self.num_pulls
     }
@@ -56,7 +181,7 @@ impl Bandit {
 // implement debug for Bandit
 impl std::fmt::Debug for Bandit {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Bandit: {} - Value: {} - Variance: {}", self.name, self.value, self.variance)
+        write!(f, "Bandit: {} - Value: {}", self.name, self.value)
     }
 }
 
@@ -64,21 +189,85 @@ pub struct KArmedBandit {
     bandits: Vec<Bandit>, // list of bandits
     k: usize, // number of bandits
     num_pulls: usize,
+    /// standard deviation of the random walk of the true values, stationary when unset
+    random_walk_standard_deviation: Option<f64>,
 }
 
 impl KArmedBandit {
     pub fn new(k: usize) -> KArmedBandit {
+        KArmedBandit::with_rng(k, StdRng::from_entropy())
+    }
+
+    ///
+    /// Create a k armed bandit whose true values and rewards are reproducible
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - usize - number of bandits
+    /// * `seed` - u64 - seed for the bandits' true values and rewards
+    pub fn with_seed(k: usize, seed: u64) -> KArmedBandit {
+        KArmedBandit::with_rng(k, StdRng::seed_from_u64(seed))
+    }
+
+    ///
+    /// Create a k armed bandit with one bandit of each given type
+    ///
+    /// # Arguments
+    ///
+    /// * `arms` - Vec<BanditType> - reward distribution of every bandit
+    pub fn from_arms(arms: Vec<BanditType>) -> KArmedBandit {
+        KArmedBandit::from_arms_with_rng(arms, StdRng::from_entropy())
+    }
+
+    ///
+    /// Create a k armed bandit with one bandit of each given type, whose rewards are reproducible
+    ///
+    /// # Arguments
+    ///
+    /// * `arms` - Vec<BanditType> - reward distribution of every bandit
+    /// * `seed` - u64 - seed for the bandits' true values and rewards
+    pub fn from_arms_with_seed(arms: Vec<BanditType>, seed: u64) -> KArmedBandit {
+        KArmedBandit::from_arms_with_rng(arms, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(k: usize, rng: StdRng) -> KArmedBandit {
+        let arms = (0..k).map(|_| BanditType::Gaussian).collect();
+        KArmedBandit::from_arms_with_rng(arms, rng)
+    }
+
+    fn from_arms_with_rng(arms: Vec<BanditType>, mut rng: StdRng) -> KArmedBandit {
+        let k = arms.len();
         let mut bandits = vec![];
-        for i in 0..k {
-            bandits.push(Bandit::new(i.to_string(), BanditType::Gaussian));
+        for (i, bandit_type) in arms.into_iter().enumerate() {
+            // every bandit gets its own stream so rewards do not depend on pull order
+            let bandit_rng = StdRng::seed_from_u64(rng.gen());
+            bandits.push(Bandit::with_rng(i.to_string(), bandit_type, bandit_rng));
         }
         KArmedBandit {
             bandits,
             k,
             num_pulls: 0,
+            random_walk_standard_deviation: None,
         }
     }
 
+    ///
+    /// Make the problem nonstationary as in Sutton & Barto Exercise 2.5. Every true value
+    /// keeps its starting point and takes an independent random walk step after each pull,
+    /// the book starts them all at 0 by using `Normal` arms with mean 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `standard_deviation` - f64 - standard deviation of every step, 0.01 in the book
+    pub fn with_random_walk(mut self, standard_deviation: f64) -> KArmedBandit {
+        assert!(
+            standard_deviation >= 0.0,
+            "random walk standard deviation must not be negative"
+        );
+        self.random_walk_standard_deviation = Some(standard_deviation);
+        self
+    }
+
     ///
     /// Pull a bandit by index and return the reward
     ///
@@ -92,7 +281,13 @@ impl KArmedBandit {
     ///
     pub fn pull_by_index(&mut self, index: usize) -> f64 {
         self.num_pulls += 1;
-        self.bandits[index].pull()
+        let reward = self.bandits[index].pull();
+        if let Some(standard_deviation) = self.random_walk_standard_deviation {
+            for bandit in self.bandits.iter_mut() {
+                bandit.random_walk(standard_deviation);
+            }
+        }
+        reward
     }
 
     ///
@@ -137,9 +332,53 @@ impl KArmedBandit {
     pub fn num_bandits(&self) -> usize {
         self.k
     }
+
+    ///
+    /// Get the true value of a bandit by index
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - usize - index of bandit
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - expected reward of pulling the bandit
+    pub fn get_value_by_index(&self, index: usize) -> f64 {
+        self.bandits[index].get_value()
+    }
+
+    ///
+    /// Get the index of the bandit with the highest true value, ties go to the first one
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - index of the optimal bandit
+    pub fn optimal_action(&self) -> usize {
+        let mut best = 0;
+        for (i, bandit) in self.bandits.iter().enumerate() {
+            if bandit.get_value() > self.bandits[best].get_value() {
+                best = i;
+            }
+        }
+        best
+    }
+
+    ///
+    /// Get the highest true value of any bandit
+    ///
+    /// # Returns
+    ///
+    /// * `f64` - expected reward of pulling the optimal bandit
+    pub fn optimal_value(&self) -> f64 {
+        self.get_value_by_index(self.optimal_action())
+    }
 }
 
 impl Environment for KArmedBandit {
+    // there is a single state for this environment and actions are bandit indices
+    type State = usize;
+    type Action = usize;
+
     fn reset(&mut self) {
         self.num_pulls = 0;
         for bandit in self.bandits.iter_mut() {
@@ -148,24 +387,20 @@ impl Environment for KArmedBandit {
         }
     }
 
-    fn all_possible_states(&self) -> Vec<String> {
-        vec!["0".to_string()]
+    fn all_possible_states(&self) -> Vec<usize> {
+        vec![0]
     }
     fn step(&mut self, action: usize) -> f64 {
         self.pull_by_index(action)
     }
 
     // state is always 0 for this environment
-    fn get_state(&self) -> String {
-        "0".to_string()
+    fn get_state(&self) -> usize {
+        0
     }
 
     fn get_actions(&self) -> Vec<usize> {
-        let mut actions = vec![];
-        for i in 0..self.k {
-            actions.push(i);
-        }
-        actions
+        (0..self.k).collect()
     }
 
     fn is_terminal(&self) -> bool {
@@ -183,4 +418,12 @@ impl Environment for KArmedBandit {
     fn get_total_number_of_actions_taken(&self) -> usize {
         self.num_pulls
     }
+
+    fn is_optimal_action(&self, action: &usize) -> Option<bool> {
+        Some(self.get_value_by_index(*action) == self.optimal_value())
+    }
+
+    fn regret(&self, action: &usize) -> Option<f64> {
+        Some(self.optimal_value() - self.get_value_by_index(*action))
+    }
 }
@@ -1,32 +1,52 @@
-use rand::Rng;
+use rand::{ rngs::StdRng, Rng, SeedableRng };
+use rustc_hash::FxHashMap;
+use serde::{ Deserialize, Serialize };
 
-use crate::environment::Environment;
+use crate::environment::{ Environment, Model, ResetToState, Snapshot, Transition };
 
 pub struct Blackjack {
     pub dealer: Dealer,
     pub player: Player,
     pub is_player_turn: bool,
     pub step_count: usize,
+    rng: StdRng,
 }
 
+#[derive(Clone)]
 pub struct Dealer {
     hand: Vec<Card>,
+    usable_ace: bool,
     sum: i32,
 }
 
+#[derive(Clone)]
 pub struct Player {
     hand: Vec<Card>,
     usable_ace: bool,
     sum: i32,
 }
 
+#[derive(Clone)]
 pub struct Card {
     value: i32,
-    suit: String,
+}
+
+/// State observed by the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlackjackState {
+    pub player_sum: i32,
+    pub dealer_showing: i32,
+    pub usable_ace: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BlackjackAction {
+    Hit,
+    Stick,
 }
 
 pub trait BlackJackPlayer {
-    fn draw_card(&mut self);
+    fn draw_card<R: Rng + ?Sized>(&mut self, rng: &mut R);
     fn get_sum(&self) -> i32;
     fn did_bust(&self) -> bool {
         self.get_sum() > 21
@@ -35,64 +55,95 @@ pub trait BlackJackPlayer {
 
 impl Blackjack {
     pub fn new() -> Blackjack {
-        Blackjack {
+        Blackjack::with_rng(StdRng::from_entropy())
+    }
+
+    ///
+    /// Create a blackjack game whose cards are dealt reproducibly
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - u64 - seed for the deck
+    pub fn with_seed(seed: u64) -> Blackjack {
+        Blackjack::with_rng(StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(rng: StdRng) -> Blackjack {
+        let mut blackjack = Blackjack {
             dealer: Dealer::new(),
             player: Player::new(),
             is_player_turn: true,
             step_count: 0,
-        }
+            rng,
+        };
+        // deal the opening cards so the first episode starts from a valid state
+        blackjack.reset();
+        blackjack
+    }
+}
+
+impl Default for Blackjack {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Environment for Blackjack {
+    type State = BlackjackState;
+    type Action = BlackjackAction;
+
     fn reset(&mut self) {
         self.dealer = Dealer::new();
         self.player = Player::new();
         // draw two cards for player and dealer
-        self.player.draw_card();
-        self.dealer.draw_card();
-        self.player.draw_card();
+        self.player.draw_card(&mut self.rng);
+        self.dealer.draw_card(&mut self.rng);
+        self.player.draw_card(&mut self.rng);
+        // below 12 no card can bust the player, so hitting is always right. Dealing up to 12
+        // leaves only the 200 states of Sutton & Barto Example 5.1 to learn
+        while self.player.get_sum() < 12 {
+            self.player.draw_card(&mut self.rng);
+        }
         self.is_player_turn = true;
         self.step_count = 0;
     }
-    fn all_possible_states(&self) -> Vec<String> {
+    fn all_possible_states(&self) -> Vec<BlackjackState> {
         let mut states = Vec::new();
-        for i in 12..22 {
-            for j in 2..12 {
-                for k in 0..2 {
-                    states.push(encode_state(i, j, k == 1));
+        for player_sum in 12..22 {
+            for dealer_showing in 2..12 {
+                for usable_ace in [false, true] {
+                    states.push(BlackjackState {
+                        player_sum,
+                        dealer_showing,
+                        usable_ace,
+                    });
                 }
             }
         }
         states
     }
-    fn step(&mut self, action: usize) -> f64 {
+    fn step(&mut self, action: BlackjackAction) -> f64 {
         self.step_count += 1;
 
-        if self.is_player_turn {
-            match action {
-                // hit case
-                0 => self.player.draw_card(),
-                // stay case
-                1 => {
-                    self.is_player_turn = false;
+        match action {
+            BlackjackAction::Hit => self.player.draw_card(&mut self.rng),
+            BlackjackAction::Stick => {
+                self.is_player_turn = false;
+                // the dealer plays out its fixed strategy as soon as the player sticks, so
+                // sticking ends the episode and its reward is credited to the stick itself
+                while self.dealer.get_sum() < 17 {
+                    self.dealer.draw_card(&mut self.rng);
                 }
-                _ => panic!("Invalid action"),
-            }
-        } else {
-            println!("Dealer's turn");
-            // play fixed dealer strategy if not player's turn
-            while self.dealer.get_sum() < 17 {
-                self.dealer.draw_card();
             }
         }
         // return reward
         if self.is_terminal() {
             if self.player.did_bust() {
                 return -1.0;
-            } else if self.dealer.did_bust() {
-                return 1.0;
-            } else if self.player.get_sum() > self.dealer.get_sum() {
+            } else if
+                self.dealer.did_bust() ||
+                self.player.get_sum() > self.dealer.get_sum()
+            {
                 return 1.0;
             } else if self.player.get_sum() < self.dealer.get_sum() {
                 return -1.0;
@@ -103,17 +154,17 @@ impl Environment for Blackjack {
         0.0
     }
 
-    fn get_state(&self) -> String {
-        let player_sum = self.player.get_sum();
-        let dealer_sum = self.dealer.get_sum();
-        let usable_ace = self.player.usable_ace;
-        // here we are using a simple encoding of the state
-        let state = encode_state(player_sum, dealer_sum, usable_ace);
-        state
+    fn get_state(&self) -> BlackjackState {
+        BlackjackState {
+            player_sum: self.player.get_sum(),
+            // the dealer holds a single card while the player is acting
+            dealer_showing: self.dealer.get_sum(),
+            usable_ace: self.player.usable_ace,
+        }
     }
 
-    fn get_actions(&self) -> Vec<usize> {
-        vec![0, 1]
+    fn get_actions(&self) -> Vec<BlackjackAction> {
+        vec![BlackjackAction::Hit, BlackjackAction::Stick]
     }
 
     fn is_terminal(&self) -> bool {
@@ -135,15 +186,128 @@ impl Environment for Blackjack {
     }
 }
 
+impl ResetToState for Blackjack {
+    /// Start a new episode with the given player sum, usable ace and dealer card
+    fn reset_to_state(&mut self, state: &BlackjackState) {
+        self.player = Player::new();
+        self.player.sum = state.player_sum;
+        self.player.usable_ace = state.usable_ace;
+        self.dealer = Dealer::new();
+        // an ace is showing as 11
+        let dealer_card = if state.dealer_showing == 11 { 1 } else { state.dealer_showing };
+        add_card(&mut self.dealer.sum, &mut self.dealer.usable_ace, dealer_card);
+        self.dealer.hand.push(Card { value: dealer_card });
+        self.is_player_turn = true;
+        self.step_count = 0;
+    }
+}
+
+impl Snapshot for Blackjack {
+    fn snapshot(&self, seed: u64) -> Blackjack {
+        Blackjack {
+            dealer: self.dealer.clone(),
+            player: self.player.clone(),
+            is_player_turn: self.is_player_turn,
+            step_count: self.step_count,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Model for Blackjack {
+    fn actions_in(&self, _state: &BlackjackState) -> Vec<BlackjackAction> {
+        vec![BlackjackAction::Hit, BlackjackAction::Stick]
+    }
+
+    /// Outcomes with an infinite deck. Terminal transitions keep the state the player acted in.
+    fn transitions(
+        &self,
+        state: &BlackjackState,
+        action: &BlackjackAction
+    ) -> Vec<Transition<BlackjackState>> {
+        match action {
+            BlackjackAction::Hit =>
+                card_probabilities()
+                    .into_iter()
+                    .map(|(value, probability)| {
+                        let mut player_sum = state.player_sum;
+                        let mut usable_ace = state.usable_ace;
+                        add_card(&mut player_sum, &mut usable_ace, value);
+                        if player_sum > 21 {
+                            Transition {
+                                probability,
+                                next_state: *state,
+                                reward: -1.0,
+                                is_terminal: true,
+                            }
+                        } else {
+                            Transition {
+                                probability,
+                                next_state: BlackjackState {
+                                    player_sum,
+                                    dealer_showing: state.dealer_showing,
+                                    usable_ace,
+                                },
+                                reward: 0.0,
+                                is_terminal: false,
+                            }
+                        }
+                    })
+                    .collect(),
+            BlackjackAction::Stick => {
+                let mut dealer_sum = 0;
+                let mut dealer_usable_ace = false;
+                // an ace is showing as 11
+                let dealer_card = if state.dealer_showing == 11 { 1 } else { state.dealer_showing };
+                add_card(&mut dealer_sum, &mut dealer_usable_ace, dealer_card);
+                // probabilities of losing, drawing and winning
+                let mut outcome_probabilities = [0.0; 3];
+                for (final_sum, probability) in dealer_outcomes(
+                    dealer_sum,
+                    dealer_usable_ace,
+                    &mut FxHashMap::default()
+                ) {
+                    let outcome = if final_sum > 21 || state.player_sum > final_sum {
+                        2
+                    } else if state.player_sum == final_sum {
+                        1
+                    } else {
+                        0
+                    };
+                    outcome_probabilities[outcome] += probability;
+                }
+                outcome_probabilities
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, probability)| **probability > 0.0)
+                    .map(|(outcome, probability)| Transition {
+                        probability: *probability,
+                        next_state: *state,
+                        reward: (outcome as f64) - 1.0,
+                        is_terminal: true,
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
 impl Dealer {
     pub fn new() -> Dealer {
         Dealer {
             hand: Vec::new(),
+            usable_ace: false,
             sum: 0,
         }
     }
 }
 
+impl Default for Dealer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Player {
     pub fn new() -> Player {
         Player {
@@ -154,34 +318,17 @@ impl Player {
     }
 }
 
-// we have 10 possible player sums (12-21), 19
-pub fn encode_state(player_sum: i32, dealer_sum: i32, usable_ace: bool) -> String {
-    let mut state = String::new();
-    state.push_str(&player_sum.to_string());
-    state.push_str("-");
-    state.push_str(&dealer_sum.to_string());
-    state.push_str("-");
-    state.push_str(&usable_ace.to_string());
-    state
-}
-
-pub fn decode_state(state: String) -> (usize, usize, bool) {
-    let parts: Vec<&str> = state.split("-").collect();
-    let player_sum = parts[0].parse::<usize>().unwrap();
-    let dealer_sum = parts[1].parse::<usize>().unwrap();
-    let usable_ace = parts[2].parse::<bool>().unwrap();
-    (player_sum, dealer_sum, usable_ace)
+impl Default for Player {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl BlackJackPlayer for Dealer {
-    fn draw_card(&mut self) {
+    fn draw_card<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         // draw card from deck
-        let mut card = random_card();
-        // if card is an ace and the sum is less than or equal to 10, add 11 to the sum
-        if card.value == 1 && self.sum <= 10 {
-            card.value = 11;
-        }
-        self.sum += card.value;
+        let card = random_card(rng);
+        add_card(&mut self.sum, &mut self.usable_ace, card.value);
         self.hand.push(card);
     }
 
@@ -191,20 +338,10 @@ impl BlackJackPlayer for Dealer {
 }
 
 impl BlackJackPlayer for Player {
-    fn draw_card(&mut self) {
+    fn draw_card<R: Rng + ?Sized>(&mut self, rng: &mut R) {
         // draw card from deck
-        let mut card = random_card();
-        // if card is an ace and the sum is less than or equal to 10, add 11 to the sum
-        if card.value == 1 && self.sum <= 10 {
-            card.value = 11;
-            self.usable_ace = true;
-        }
-        // if we have a usable ace and the sum is greater than 21, subtract 10 from the sum
-        if self.sum + card.value > 21 && self.usable_ace {
-            self.sum -= 10;
-            self.usable_ace = false;
-        }
-        self.sum += card.value;
+        let card = random_card(rng);
+        add_card(&mut self.sum, &mut self.usable_ace, card.value);
         self.hand.push(card);
     }
 
@@ -213,12 +350,70 @@ impl BlackJackPlayer for Player {
     }
 }
 
-fn random_card() -> Card {
-    let mut rng = rand::thread_rng();
-    let value = rng.gen_range(1..11);
-    let suit = String::from("hearts");
+/// Add a card to a hand total, counting aces as 11 while that does not bust the hand
+fn add_card(sum: &mut i32, usable_ace: &mut bool, value: i32) {
+    let mut value = value;
+    // if card is an ace and the sum is less than or equal to 10, add 11 to the sum
+    if value == 1 && *sum <= 10 {
+        value = 11;
+        *usable_ace = true;
+    }
+    // if we have a usable ace and the sum is greater than 21, subtract 10 from the sum
+    if *sum + value > 21 && *usable_ace {
+        *sum -= 10;
+        *usable_ace = false;
+    }
+    *sum += value;
+}
+
+/// Draw a card from an infinite deck as in Sutton & Barto Example 5.1. Jacks, queens and
+/// kings count as 10, so a 10 is drawn with probability 4/13 and every other value with 1/13,
+/// matching the distribution the `Model` implementation computes exact values with
+fn random_card<R: Rng + ?Sized>(rng: &mut R) -> Card {
+    let rank = rng.gen_range(1..14);
     Card {
-        value,
-        suit,
+        value: rank.min(10),
+    }
+}
+
+/// Card values of an infinite deck along with their probabilities
+fn card_probabilities() -> Vec<(i32, f64)> {
+    (1..11).map(|value| (value, if value == 10 { 4.0 / 13.0 } else { 1.0 / 13.0 })).collect()
+}
+
+///
+/// Distribution of the final sums of a dealer who hits below 17, busted sums included
+///
+/// # Arguments
+///
+/// * `sum` - i32 - current sum of the dealer
+/// * `usable_ace` - bool - whether the dealer counts an ace as 11
+/// * `cache` - &mut FxHashMap - distributions already computed for a sum and usable ace
+fn dealer_outcomes(
+    sum: i32,
+    usable_ace: bool,
+    cache: &mut FxHashMap<(i32, bool), Vec<(i32, f64)>>
+) -> Vec<(i32, f64)> {
+    if sum >= 17 {
+        return vec![(sum, 1.0)];
+    }
+    if let Some(outcomes) = cache.get(&(sum, usable_ace)) {
+        return outcomes.clone();
+    }
+    let mut outcomes: Vec<(i32, f64)> = Vec::new();
+    for (value, probability) in card_probabilities() {
+        let mut next_sum = sum;
+        let mut next_usable_ace = usable_ace;
+        add_card(&mut next_sum, &mut next_usable_ace, value);
+        for (final_sum, final_probability) in dealer_outcomes(next_sum, next_usable_ace, cache) {
+            match outcomes.iter_mut().find(|(outcome, _)| *outcome == final_sum) {
+                Some((_, total)) => {
+                    *total += probability * final_probability;
+                }
+                None => outcomes.push((final_sum, probability * final_probability)),
+            }
+        }
     }
+    cache.insert((sum, usable_ace), outcomes.clone());
+    outcomes
 }
@@ -0,0 +1,806 @@
+use std::{ collections::VecDeque, fmt };
+
+use rand::{ rngs::StdRng, Rng, SeedableRng };
+use rustc_hash::FxHashMap;
+use serde::{ Deserialize, Serialize };
+
+use crate::environment::{ Environment, Model, ResetToState, Snapshot, Transition };
+
+/// Cell of the grid as (row, column), rows counted from the top
+pub type GridPosition = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GridAction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl GridAction {
+    /// Change of (row, column) the action makes
+    fn offset(&self) -> (isize, isize) {
+        match self {
+            GridAction::Up => (-1, 0),
+            GridAction::Down => (1, 0),
+            GridAction::Left => (0, -1),
+            GridAction::Right => (0, 1),
+            GridAction::UpLeft => (-1, -1),
+            GridAction::UpRight => (-1, 1),
+            GridAction::DownLeft => (1, -1),
+            GridAction::DownRight => (1, 1),
+        }
+    }
+
+    /// Arrow drawn for the action when rendering a policy
+    fn arrow(&self) -> char {
+        match self {
+            GridAction::Up => '↑',
+            GridAction::Down => '↓',
+            GridAction::Left => '←',
+            GridAction::Right => '→',
+            GridAction::UpLeft => '↖',
+            GridAction::UpRight => '↗',
+            GridAction::DownLeft => '↙',
+            GridAction::DownRight => '↘',
+        }
+    }
+}
+
+/// Actions available in every cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveSet {
+    /// up, down, left and right
+    Cardinal,
+    /// cardinal and diagonal moves, as in Sutton & Barto Exercise 6.9
+    KingsMoves,
+}
+
+/// Cell of a gridworld map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridCell {
+    /// `.`, or `S` for the start cell
+    Empty,
+    /// `#`, cannot be entered
+    Wall,
+    /// `G`, entering it ends the episode
+    Goal,
+    /// `C`, entering it sends the agent back to the start
+    Cliff,
+    /// digit, moves leaving the cell are pushed up by this many cells
+    Wind(usize),
+    /// lowercase letter, entering it moves the agent to the other cell with the same letter
+    Teleport(char),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GridworldConfig {
+    pub moves: MoveSet,
+    /// reward of every step that enters neither a goal nor a cliff
+    pub step_reward: f64,
+    /// reward of the step that enters a goal
+    pub goal_reward: f64,
+    /// reward of the step that enters a cliff
+    pub cliff_reward: f64,
+    /// whether non-zero wind is one weaker or stronger than its cell says, each a third of the
+    /// time, as in Sutton & Barto Exercise 6.10
+    pub stochastic_wind: bool,
+}
+
+impl Default for GridworldConfig {
+    fn default() -> Self {
+        GridworldConfig {
+            moves: MoveSet::Cardinal,
+            step_reward: -1.0,
+            goal_reward: -1.0,
+            cliff_reward: -100.0,
+            stochastic_wind: false,
+        }
+    }
+}
+
+/// Walls that replace the walls of a gridworld once the given number of steps have been taken
+#[derive(Debug, Clone)]
+pub struct WallChange {
+    pub at_step: usize,
+    pub walls: Vec<GridPosition>,
+}
+
+/// Reasons an ASCII map cannot be turned into a gridworld
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    Empty,
+    /// a row has a different number of cells than the first row
+    UnevenRow(usize),
+    UnknownCell(char),
+    MissingStart,
+    MultipleStarts,
+    /// a teleport letter does not appear exactly twice
+    UnpairedTeleport(char),
+}
+
+impl fmt::Display for GridParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "map has no cells"),
+            GridParseError::UnevenRow(row) => {
+                write!(f, "row {} has a different length than the first row", row)
+            }
+            GridParseError::UnknownCell(cell) => write!(f, "unknown cell '{}'", cell),
+            GridParseError::MissingStart => write!(f, "map has no start cell 'S'"),
+            GridParseError::MultipleStarts => write!(f, "map has more than one start cell 'S'"),
+            GridParseError::UnpairedTeleport(letter) => {
+                write!(f, "teleport '{}' does not appear exactly twice", letter)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GridParseError {}
+
+/// Episodic gridworld parsed from an ASCII map, covering the gridworlds of Sutton & Barto.
+///
+/// Moves into a wall or off the grid leave the agent in place. Wind pushes moves that leave a
+/// windy cell upwards, one cell at a time, stopping at walls and the edge of the grid. Cliffs
+/// send the agent back to the start without ending the episode, and teleports move an agent
+/// entering them to their paired cell. The walls can change once after a number of steps,
+/// counted across episodes, to model the changing mazes of Sutton & Barto section 8.3.
+#[derive(Clone)]
+pub struct Gridworld {
+    cells: Vec<Vec<GridCell>>,
+    start: GridPosition,
+    teleports: FxHashMap<GridPosition, GridPosition>,
+    config: GridworldConfig,
+    wall_change: Option<WallChange>,
+    position: GridPosition,
+    step_count: usize,
+    /// steps taken across all episodes, as used to schedule the change of walls
+    total_steps: usize,
+    rng: StdRng,
+}
+
+impl Gridworld {
+    ///
+    /// Create a gridworld from an ASCII map with one row per line. Blank lines and
+    /// surrounding whitespace are ignored.
+    ///
+    /// `.` empty, `S` start, `G` goal, `#` wall, `C` cliff, `0`-`9` upward wind,
+    /// `a`-`z` teleport to the other cell with the same letter
+    ///
+    /// # Arguments
+    ///
+    /// * `map` - &str - rows of the grid
+    /// * `config` - GridworldConfig - moves, rewards and wind
+    pub fn parse(map: &str, config: GridworldConfig) -> Result<Gridworld, GridParseError> {
+        let rows: Vec<&str> = map
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        if rows.is_empty() {
+            return Err(GridParseError::Empty);
+        }
+        let columns = rows[0].chars().count();
+        let mut cells = Vec::with_capacity(rows.len());
+        let mut start = None;
+        let mut teleport_cells: FxHashMap<char, Vec<GridPosition>> = FxHashMap::default();
+        for (row, line) in rows.iter().enumerate() {
+            if line.chars().count() != columns {
+                return Err(GridParseError::UnevenRow(row));
+            }
+            let mut row_cells = Vec::with_capacity(columns);
+            for (column, symbol) in line.chars().enumerate() {
+                let cell = match symbol {
+                    '.' => GridCell::Empty,
+                    'S' => {
+                        if start.is_some() {
+                            return Err(GridParseError::MultipleStarts);
+                        }
+                        start = Some((row, column));
+                        GridCell::Empty
+                    }
+                    'G' => GridCell::Goal,
+                    '#' => GridCell::Wall,
+                    'C' => GridCell::Cliff,
+                    '0'..='9' => {
+                        let strength = symbol.to_digit(10).unwrap() as usize;
+                        if strength == 0 { GridCell::Empty } else { GridCell::Wind(strength) }
+                    }
+                    'a'..='z' => {
+                        teleport_cells.entry(symbol).or_default().push((row, column));
+                        GridCell::Teleport(symbol)
+                    }
+                    _ => {
+                        return Err(GridParseError::UnknownCell(symbol));
+                    }
+                };
+                row_cells.push(cell);
+            }
+            cells.push(row_cells);
+        }
+        let start = start.ok_or(GridParseError::MissingStart)?;
+        let mut teleports = FxHashMap::default();
+        for (letter, positions) in teleport_cells {
+            if positions.len() != 2 {
+                return Err(GridParseError::UnpairedTeleport(letter));
+            }
+            teleports.insert(positions[0], positions[1]);
+            teleports.insert(positions[1], positions[0]);
+        }
+        Ok(Gridworld {
+            cells,
+            start,
+            teleports,
+            config,
+            wall_change: None,
+            position: start,
+            step_count: 0,
+            total_steps: 0,
+            rng: StdRng::from_entropy(),
+        })
+    }
+
+    ///
+    /// Replace the walls once the given number of steps have been taken. An agent left inside
+    /// one of the new walls is moved to the nearest cell that is not a wall.
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - WallChange - step of the change and the new walls
+    pub fn with_wall_change(mut self, change: WallChange) -> Gridworld {
+        self.wall_change = Some(change);
+        self
+    }
+
+    ///
+    /// Draw stochastic wind reproducibly
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - u64 - seed for the wind
+    pub fn with_seed(mut self, seed: u64) -> Gridworld {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// 4x4 gridworld of Sutton & Barto Example 4.1, with terminal cells in two opposite corners.
+    /// Episodes start in one of the other corners.
+    pub fn dp_gridworld() -> Gridworld {
+        let map = "
+            G..S
+            ....
+            ....
+            ...G
+        ";
+        Gridworld::parse(map, GridworldConfig::default()).unwrap()
+    }
+
+    /// Cliff walking of Sutton & Barto Example 6.6
+    pub fn cliff_walking() -> Gridworld {
+        let map = "
+            ............
+            ............
+            ............
+            SCCCCCCCCCCG
+        ";
+        Gridworld::parse(map, GridworldConfig::default()).unwrap()
+    }
+
+    ///
+    /// Windy gridworld of Sutton & Barto Example 6.5
+    ///
+    /// # Arguments
+    ///
+    /// * `moves` - MoveSet - cardinal moves, or king's moves for Exercise 6.9
+    /// * `stochastic_wind` - bool - whether the wind varies, for Exercise 6.10
+    pub fn windy_gridworld(moves: MoveSet, stochastic_wind: bool) -> Gridworld {
+        let map = "
+            ...1112210
+            ...1112210
+            ...1112210
+            S..1112G10
+            ...1112210
+            ...1112210
+            ...1112210
+        ";
+        let config = GridworldConfig {
+            moves,
+            stochastic_wind,
+            ..GridworldConfig::default()
+        };
+        Gridworld::parse(map, config).unwrap()
+    }
+
+    /// Maze of Sutton & Barto Figure 8.2
+    pub fn dyna_maze() -> Gridworld {
+        let map = "
+            .......#G
+            ..#....#.
+            S.#....#.
+            ..#......
+            .....#...
+            .........
+        ";
+        Gridworld::parse(map, maze_config()).unwrap()
+    }
+
+    ///
+    /// Maze of Sutton & Barto Figure 8.4, the short path around the wall is blocked
+    /// and a longer one opens after `change_at_step` steps
+    ///
+    /// # Arguments
+    ///
+    /// * `change_at_step` - usize - number of steps before the change
+    pub fn blocking_maze(change_at_step: usize) -> Gridworld {
+        let map = "
+            ........G
+            .........
+            .........
+            ########.
+            .........
+            ...S.....
+        ";
+        Gridworld::parse(map, maze_config()).unwrap().with_wall_change(WallChange {
+            at_step: change_at_step,
+            walls: (1..9).map(|column| (3, column)).collect(),
+        })
+    }
+
+    ///
+    /// Maze of Sutton & Barto Figure 8.5, a shortcut opens after `change_at_step` steps
+    ///
+    /// # Arguments
+    ///
+    /// * `change_at_step` - usize - number of steps before the change
+    pub fn shortcut_maze(change_at_step: usize) -> Gridworld {
+        let map = "
+            ........G
+            .........
+            .........
+            .########
+            .........
+            ...S.....
+        ";
+        Gridworld::parse(map, maze_config()).unwrap().with_wall_change(WallChange {
+            at_step: change_at_step,
+            walls: (1..8).map(|column| (3, column)).collect(),
+        })
+    }
+
+    ///
+    /// Get a finer grained copy of the gridworld, as used by Sutton & Barto Example 8.4. Every
+    /// cell becomes a `factor` by `factor` block of the same cell, except that the start, goals
+    /// and teleports only take the top left cell of their block
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - usize - number of rows and columns every cell is split into
+    pub fn scale(&self, factor: usize) -> Gridworld {
+        let scale_position = |(row, column): GridPosition| (row * factor, column * factor);
+        let mut cells = vec![
+            vec![GridCell::Empty; self.get_columns() * factor];
+            self.get_rows() * factor
+        ];
+        for (row, row_cells) in self.cells.iter().enumerate() {
+            for (column, cell) in row_cells.iter().enumerate() {
+                let (top, left) = scale_position((row, column));
+                for i in 0..factor {
+                    for j in 0..factor {
+                        cells[top + i][left + j] = match cell {
+                            GridCell::Goal | GridCell::Teleport(_) if i + j > 0 => GridCell::Empty,
+                            _ => *cell,
+                        };
+                    }
+                }
+            }
+        }
+        let scale_walls = |walls: &[GridPosition]| -> Vec<GridPosition> {
+            let mut scaled_walls = Vec::with_capacity(walls.len() * factor * factor);
+            for position in walls {
+                let (top, left) = scale_position(*position);
+                for i in 0..factor {
+                    for j in 0..factor {
+                        scaled_walls.push((top + i, left + j));
+                    }
+                }
+            }
+            scaled_walls
+        };
+        Gridworld {
+            cells,
+            start: scale_position(self.start),
+            teleports: self.teleports
+                .iter()
+                .map(|(entry, exit)| (scale_position(*entry), scale_position(*exit)))
+                .collect(),
+            config: self.config,
+            wall_change: self.wall_change.as_ref().map(|change| WallChange {
+                at_step: change.at_step,
+                walls: scale_walls(&change.walls),
+            }),
+            position: scale_position(self.position),
+            step_count: self.step_count,
+            total_steps: self.total_steps,
+            rng: self.rng.clone(),
+        }
+    }
+
+    /// Fewest steps from the start to a goal with the current walls and wind at its usual
+    /// strength, if a goal can be reached
+    pub fn shortest_path_length(&self) -> Option<usize> {
+        // breadth first search, every move costs one step
+        let mut distances = vec![vec![None; self.get_columns()]; self.get_rows()];
+        distances[self.start.0][self.start.1] = Some(0);
+        let mut frontier = VecDeque::from([self.start]);
+        while let Some(position) = frontier.pop_front() {
+            let distance = distances[position.0][position.1].unwrap();
+            let wind = match self.get_cell(position) {
+                GridCell::Wind(strength) => strength,
+                _ => 0,
+            };
+            for action in self.get_actions() {
+                let (next_position, _, is_terminal) = self.outcome(position, action, wind);
+                if is_terminal {
+                    return Some(distance + 1);
+                }
+                if distances[next_position.0][next_position.1].is_none() {
+                    distances[next_position.0][next_position.1] = Some(distance + 1);
+                    frontier.push_back(next_position);
+                }
+            }
+        }
+        None
+    }
+
+    pub fn get_rows(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn get_columns(&self) -> usize {
+        self.cells[0].len()
+    }
+
+    pub fn get_start(&self) -> GridPosition {
+        self.start
+    }
+
+    pub fn get_cell(&self, position: GridPosition) -> GridCell {
+        self.cells[position.0][position.1]
+    }
+
+    /// Map of the grid with the agent drawn as `@`
+    pub fn render(&self) -> String {
+        self.render_cells(|position, cell| {
+            if position == self.position { '@' } else { self.cell_symbol(position, cell) }
+        })
+    }
+
+    ///
+    /// Map of the grid with the action of a policy drawn as an arrow in every cell the agent can
+    /// act in. Windy cells and the start are drawn over, other cells keep their symbol.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - F - action taken in a cell, e.g. greedy with respect to learned estimates
+    pub fn render_policy<F: Fn(&GridPosition) -> GridAction>(&self, policy: F) -> String {
+        self.render_cells(|position, cell| {
+            match cell {
+                GridCell::Empty | GridCell::Wind(_) => policy(&position).arrow(),
+                _ => self.cell_symbol(position, cell),
+            }
+        })
+    }
+
+    ///
+    /// Map of the grid with the cells of a path drawn as `*`
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - &[GridPosition] - cells to mark
+    pub fn render_path(&self, path: &[GridPosition]) -> String {
+        self.render_cells(|position, cell| {
+            if path.contains(&position) && cell != GridCell::Goal {
+                '*'
+            } else {
+                self.cell_symbol(position, cell)
+            }
+        })
+    }
+
+    ///
+    /// Cells visited when following a policy from the start, with wind at its usual strength.
+    /// The path ends at a goal or after `max_steps` moves, as a policy may never reach one.
+    ///
+    /// # Arguments
+    ///
+    /// * `policy` - F - action taken in a cell
+    /// * `max_steps` - usize - number of moves after which the path is cut off
+    pub fn follow_policy<F: Fn(&GridPosition) -> GridAction>(
+        &self,
+        policy: F,
+        max_steps: usize
+    ) -> Vec<GridPosition> {
+        let mut position = self.start;
+        let mut path = vec![position];
+        for _ in 0..max_steps {
+            let wind = match self.get_cell(position) {
+                GridCell::Wind(strength) => strength,
+                _ => 0,
+            };
+            let (next_position, _, is_terminal) = self.outcome(position, policy(&position), wind);
+            position = next_position;
+            path.push(position);
+            if is_terminal {
+                break;
+            }
+        }
+        path
+    }
+
+    fn render_cells<F: Fn(GridPosition, GridCell) -> char>(&self, symbol: F) -> String {
+        let mut rendered = String::new();
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                rendered.push(symbol((row, column), *cell));
+            }
+            rendered.push('\n');
+        }
+        rendered
+    }
+
+    fn cell_symbol(&self, position: GridPosition, cell: GridCell) -> char {
+        match cell {
+            GridCell::Empty if position == self.start => 'S',
+            GridCell::Empty => '.',
+            GridCell::Wall => '#',
+            GridCell::Goal => 'G',
+            GridCell::Cliff => 'C',
+            GridCell::Wind(strength) => char::from_digit(strength as u32, 10).unwrap_or('9'),
+            GridCell::Teleport(letter) => letter,
+        }
+    }
+
+    /// Replace the walls with those of the wall change, moving the agent out of any new wall
+    fn change_walls(&mut self, walls: &[GridPosition]) {
+        for cell in self.cells.iter_mut().flatten() {
+            if *cell == GridCell::Wall {
+                *cell = GridCell::Empty;
+            }
+        }
+        for (row, column) in walls {
+            self.cells[*row][*column] = GridCell::Wall;
+        }
+        if self.get_cell(self.position) == GridCell::Wall {
+            self.position = self.nearest_open_cell(self.position);
+        }
+    }
+
+    /// Closest cell to a position that is not a wall, by number of up, down, left or right moves
+    /// ignoring walls, ties going to the first cell found in that order of moves
+    fn nearest_open_cell(&self, position: GridPosition) -> GridPosition {
+        let mut visited = vec![vec![false; self.get_columns()]; self.get_rows()];
+        visited[position.0][position.1] = true;
+        let mut frontier = VecDeque::from([position]);
+        while let Some(position) = frontier.pop_front() {
+            if self.get_cell(position) != GridCell::Wall {
+                return position;
+            }
+            for action in [GridAction::Up, GridAction::Down, GridAction::Left, GridAction::Right] {
+                let offset = action.offset();
+                let row = position.0.checked_add_signed(offset.0);
+                let column = position.1.checked_add_signed(offset.1);
+                match (row, column) {
+                    (Some(row), Some(column)) if
+                        row < self.get_rows() &&
+                        column < self.get_columns() &&
+                        !visited[row][column]
+                    => {
+                        visited[row][column] = true;
+                        frontier.push_back((row, column));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        panic!("every cell of the gridworld is a wall")
+    }
+
+    fn is_goal(&self, position: GridPosition) -> bool {
+        self.get_cell(position) == GridCell::Goal
+    }
+
+    /// Cell reached by moving from a cell by an offset, staying in place when blocked
+    fn offset_position(&self, position: GridPosition, offset: (isize, isize)) -> GridPosition {
+        let row = position.0.checked_add_signed(offset.0);
+        let column = position.1.checked_add_signed(offset.1);
+        match (row, column) {
+            (Some(row), Some(column)) if
+                row < self.get_rows() &&
+                column < self.get_columns() &&
+                self.get_cell((row, column)) != GridCell::Wall
+            => (row, column),
+            _ => position,
+        }
+    }
+
+    /// Wind strengths a move leaving a cell can be pushed by, along with their probabilities
+    fn wind_strengths(&self, position: GridPosition) -> Vec<(usize, f64)> {
+        match self.get_cell(position) {
+            GridCell::Wind(strength) if self.config.stochastic_wind => {
+                vec![(strength - 1, 1.0 / 3.0), (strength, 1.0 / 3.0), (strength + 1, 1.0 / 3.0)]
+            }
+            GridCell::Wind(strength) => vec![(strength, 1.0)],
+            _ => vec![(0, 1.0)],
+        }
+    }
+
+    ///
+    /// Next cell, reward and whether the episode ends for a move pushed by a given wind
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - GridPosition - cell the move starts in
+    /// * `action` - GridAction - move taken
+    /// * `wind` - usize - number of cells the move is pushed up
+    fn outcome(
+        &self,
+        position: GridPosition,
+        action: GridAction,
+        wind: usize
+    ) -> (GridPosition, f64, bool) {
+        let mut next_position = self.offset_position(position, action.offset());
+        for _ in 0..wind {
+            next_position = self.offset_position(next_position, (-1, 0));
+        }
+        match self.get_cell(next_position) {
+            GridCell::Goal => (next_position, self.config.goal_reward, true),
+            GridCell::Cliff => (self.start, self.config.cliff_reward, false),
+            // a blocked move leaves the agent on its teleport exit instead of sending it back
+            GridCell::Teleport(_) if next_position != position => {
+                (self.teleports[&next_position], self.config.step_reward, false)
+            }
+            _ => (next_position, self.config.step_reward, false),
+        }
+    }
+}
+
+/// Rewards of the mazes of Sutton & Barto chapter 8, reaching the goal pays 1 and every
+/// other step nothing
+fn maze_config() -> GridworldConfig {
+    GridworldConfig {
+        step_reward: 0.0,
+        goal_reward: 1.0,
+        ..GridworldConfig::default()
+    }
+}
+
+impl Environment for Gridworld {
+    type State = GridPosition;
+    type Action = GridAction;
+
+    fn reset(&mut self) {
+        self.position = self.start;
+        self.step_count = 0;
+    }
+
+    fn step(&mut self, action: GridAction) -> f64 {
+        self.step_count += 1;
+        let mut wind = 0;
+        if let GridCell::Wind(strength) = self.get_cell(self.position) {
+            wind = if self.config.stochastic_wind {
+                self.rng.gen_range(strength - 1..=strength + 1)
+            } else {
+                strength
+            };
+        }
+        let (next_position, reward, _) = self.outcome(self.position, action, wind);
+        self.position = next_position;
+        self.total_steps += 1;
+        if let Some(change) = self.wall_change.take() {
+            if self.total_steps == change.at_step {
+                self.change_walls(&change.walls);
+            } else {
+                self.wall_change = Some(change);
+            }
+        }
+        reward
+    }
+
+    fn get_state(&self) -> GridPosition {
+        self.position
+    }
+
+    fn get_actions(&self) -> Vec<GridAction> {
+        let mut actions = vec![GridAction::Up, GridAction::Down, GridAction::Left, GridAction::Right];
+        if self.config.moves == MoveSet::KingsMoves {
+            actions.extend([
+                GridAction::UpLeft,
+                GridAction::UpRight,
+                GridAction::DownLeft,
+                GridAction::DownRight,
+            ]);
+        }
+        actions
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.is_goal(self.position)
+    }
+
+    fn get_number_of_possible_actions(&self) -> usize {
+        match self.config.moves {
+            MoveSet::Cardinal => 4,
+            MoveSet::KingsMoves => 8,
+        }
+    }
+
+    fn get_number_of_possible_states(&self) -> usize {
+        self.all_possible_states().len()
+    }
+
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        self.step_count
+    }
+
+    /// Cells the agent can be in, walls and cliffs excluded
+    fn all_possible_states(&self) -> Vec<GridPosition> {
+        let mut states = Vec::new();
+        for (row, cells) in self.cells.iter().enumerate() {
+            for (column, cell) in cells.iter().enumerate() {
+                if !matches!(cell, GridCell::Wall | GridCell::Cliff) {
+                    states.push((row, column));
+                }
+            }
+        }
+        states
+    }
+}
+
+impl Model for Gridworld {
+    fn actions_in(&self, state: &GridPosition) -> Vec<GridAction> {
+        if self.is_goal(*state) {
+            Vec::new()
+        } else {
+            self.get_actions()
+        }
+    }
+
+    fn transitions(
+        &self,
+        state: &GridPosition,
+        action: &GridAction
+    ) -> Vec<Transition<GridPosition>> {
+        self.wind_strengths(*state)
+            .into_iter()
+            .map(|(wind, probability)| {
+                let (next_state, reward, is_terminal) = self.outcome(*state, *action, wind);
+                Transition {
+                    probability,
+                    next_state,
+                    reward,
+                    is_terminal,
+                }
+            })
+            .collect()
+    }
+}
+
+impl ResetToState for Gridworld {
+    fn reset_to_state(&mut self, state: &GridPosition) {
+        self.position = *state;
+        self.step_count = 0;
+    }
+}
+
+impl Snapshot for Gridworld {
+    fn snapshot(&self, seed: u64) -> Gridworld {
+        Gridworld {
+            rng: StdRng::seed_from_u64(seed),
+            ..self.clone()
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use rand::{ rngs::StdRng, SeedableRng };
+use rand_distr::{ Distribution, Normal };
+use serde::{ Deserialize, Serialize };
+
+use crate::environment::{ Environment, Snapshot };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MaximizationBiasState {
+    A,
+    B,
+    Terminal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum MaximizationBiasAction {
+    /// move from A to B
+    Left,
+    /// end the episode from A
+    Right,
+    /// one of the actions available in B, every one ends the episode
+    FromB(usize),
+}
+
+/// Small MDP of Sutton & Barto Example 6.7, showing the maximization bias of Q-learning.
+///
+/// Episodes start in A. Moving right ends the episode with no reward. Moving left leads to B
+/// with no reward, and every action from B ends the episode with a reward drawn from
+/// N(-0.1, 1). Going left is therefore worse on average, but the best looking action of B
+/// is usually overestimated.
+pub struct MaximizationBias {
+    state: MaximizationBiasState,
+    num_b_actions: usize,
+    step_count: usize,
+    rng: StdRng,
+}
+
+impl MaximizationBias {
+    ///
+    /// Create the MDP
+    ///
+    /// # Arguments
+    ///
+    /// * `num_b_actions` - usize - number of actions available in B
+    pub fn new(num_b_actions: usize) -> MaximizationBias {
+        MaximizationBias::with_rng(num_b_actions, StdRng::from_entropy())
+    }
+
+    ///
+    /// Create the MDP with reproducible rewards
+    ///
+    /// # Arguments
+    ///
+    /// * `num_b_actions` - usize - number of actions available in B
+    /// * `seed` - u64 - seed for the rewards of B
+    pub fn with_seed(num_b_actions: usize, seed: u64) -> MaximizationBias {
+        MaximizationBias::with_rng(num_b_actions, StdRng::seed_from_u64(seed))
+    }
+
+    fn with_rng(num_b_actions: usize, rng: StdRng) -> MaximizationBias {
+        MaximizationBias {
+            state: MaximizationBiasState::A,
+            num_b_actions,
+            step_count: 0,
+            rng,
+        }
+    }
+}
+
+impl Environment for MaximizationBias {
+    type State = MaximizationBiasState;
+    type Action = MaximizationBiasAction;
+
+    fn reset(&mut self) {
+        self.state = MaximizationBiasState::A;
+        self.step_count = 0;
+    }
+
+    fn step(&mut self, action: MaximizationBiasAction) -> f64 {
+        self.step_count += 1;
+        match (self.state, action) {
+            (MaximizationBiasState::A, MaximizationBiasAction::Left) => {
+                self.state = MaximizationBiasState::B;
+                0.0
+            }
+            (MaximizationBiasState::A, MaximizationBiasAction::Right) => {
+                self.state = MaximizationBiasState::Terminal;
+                0.0
+            }
+            (MaximizationBiasState::B, MaximizationBiasAction::FromB(_)) => {
+                self.state = MaximizationBiasState::Terminal;
+                Normal::new(-0.1, 1.0).unwrap().sample(&mut self.rng)
+            }
+            _ => panic!("Invalid action"),
+        }
+    }
+
+    fn get_state(&self) -> MaximizationBiasState {
+        self.state
+    }
+
+    fn get_actions(&self) -> Vec<MaximizationBiasAction> {
+        match self.state {
+            MaximizationBiasState::A =>
+                vec![MaximizationBiasAction::Left, MaximizationBiasAction::Right],
+            MaximizationBiasState::B =>
+                (0..self.num_b_actions).map(MaximizationBiasAction::FromB).collect(),
+            MaximizationBiasState::Terminal => vec![],
+        }
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.state == MaximizationBiasState::Terminal
+    }
+
+    fn get_number_of_possible_actions(&self) -> usize {
+        2 + self.num_b_actions
+    }
+
+    fn get_number_of_possible_states(&self) -> usize {
+        2
+    }
+
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        self.step_count
+    }
+
+    fn all_possible_states(&self) -> Vec<MaximizationBiasState> {
+        vec![MaximizationBiasState::A, MaximizationBiasState::B]
+    }
+
+    /// Right is optimal in A, every action of B is equally good
+    fn is_optimal_action(&self, action: &MaximizationBiasAction) -> Option<bool> {
+        Some(*action != MaximizationBiasAction::Left)
+    }
+}
+
+impl Snapshot for MaximizationBias {
+    fn snapshot(&self, seed: u64) -> MaximizationBias {
+        MaximizationBias {
+            state: self.state,
+            num_b_actions: self.num_b_actions,
+            step_count: self.step_count,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
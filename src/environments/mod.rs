@@ -1,2 +1,6 @@
 pub mod bandit;
 pub mod blackjack;
+pub mod gridworld;
+pub mod maximization_bias;
+pub mod random_walk;
+pub mod tic_tac_toe;
@@ -0,0 +1,175 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::environment::{ Environment, ResetToState, Snapshot };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum RandomWalkAction {
+    Left,
+    Right,
+}
+
+/// Chain of non-terminal states numbered 1 to `num_states`, with a terminal state at each end.
+/// Episodes start in the middle and every step moves one state to the left or right.
+/// Stepping into either end pays the reward of that end, -1 on the left and 1 on the right
+/// unless configured otherwise, and all other steps pay nothing. Following a uniformly random
+/// policy gives the random walks of Sutton & Barto chapters 6 and 7.
+#[derive(Clone)]
+pub struct RandomWalk {
+    num_states: usize,
+    position: usize,
+    left_reward: f64,
+    right_reward: f64,
+    step_count: usize,
+}
+
+impl RandomWalk {
+    ///
+    /// Create a random walk
+    ///
+    /// # Arguments
+    ///
+    /// * `num_states` - usize - number of non-terminal states
+    pub fn new(num_states: usize) -> RandomWalk {
+        RandomWalk {
+            num_states,
+            position: RandomWalk::start_position(num_states),
+            left_reward: -1.0,
+            right_reward: 1.0,
+            step_count: 0,
+        }
+    }
+
+    /// 5 state walk of Sutton & Barto Example 6.2, only the right end pays a reward of 1
+    pub fn five_state() -> RandomWalk {
+        RandomWalk::new(5).with_terminal_rewards(0.0, 1.0)
+    }
+
+    /// 19 state walk of Sutton & Barto Example 7.1
+    pub fn nineteen_state() -> RandomWalk {
+        RandomWalk::new(19)
+    }
+
+    ///
+    /// Change the rewards of stepping into the terminal states
+    ///
+    /// # Arguments
+    ///
+    /// * `left_reward` - f64 - reward of stepping into the left end
+    /// * `right_reward` - f64 - reward of stepping into the right end
+    pub fn with_terminal_rewards(mut self, left_reward: f64, right_reward: f64) -> RandomWalk {
+        self.left_reward = left_reward;
+        self.right_reward = right_reward;
+        self
+    }
+
+    fn start_position(num_states: usize) -> usize {
+        num_states.div_ceil(2)
+    }
+
+    ///
+    /// Get the value of every non-terminal state under the uniformly random policy,
+    /// ordered from state 1 to `num_states`
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<f64>` - true state values, without discounting
+    pub fn true_state_values(&self) -> Vec<f64> {
+        // the probability of ending on the right grows linearly from left to right
+        (1..=self.num_states)
+            .map(|state| {
+                let right_probability = (state as f64) / ((self.num_states + 1) as f64);
+                right_probability * self.right_reward + (1.0 - right_probability) * self.left_reward
+            })
+            .collect()
+    }
+
+    ///
+    /// Root mean square error of value estimates against the true values of the random policy,
+    /// averaged over the non-terminal states
+    ///
+    /// # Arguments
+    ///
+    /// * `value_estimate` - F - estimated value of a state, e.g. an agent's `get_state_value_estimate`
+    pub fn rms_error<F: Fn(usize) -> f64>(&self, value_estimate: F) -> f64 {
+        let squared_error: f64 = self
+            .true_state_values()
+            .iter()
+            .enumerate()
+            .map(|(i, true_value)| (value_estimate(i + 1) - true_value).powi(2))
+            .sum();
+        (squared_error / (self.num_states as f64)).sqrt()
+    }
+}
+
+impl Environment for RandomWalk {
+    type State = usize;
+    type Action = RandomWalkAction;
+
+    fn reset(&mut self) {
+        self.position = RandomWalk::start_position(self.num_states);
+        self.step_count = 0;
+    }
+
+    fn step(&mut self, action: RandomWalkAction) -> f64 {
+        if self.is_terminal() {
+            panic!("Episode has terminated");
+        }
+        self.step_count += 1;
+        match action {
+            RandomWalkAction::Left => {
+                self.position -= 1;
+            }
+            RandomWalkAction::Right => {
+                self.position += 1;
+            }
+        }
+        if self.position == 0 {
+            self.left_reward
+        } else if self.position == self.num_states + 1 {
+            self.right_reward
+        } else {
+            0.0
+        }
+    }
+
+    fn get_state(&self) -> usize {
+        self.position
+    }
+
+    fn get_actions(&self) -> Vec<RandomWalkAction> {
+        vec![RandomWalkAction::Left, RandomWalkAction::Right]
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.position == 0 || self.position == self.num_states + 1
+    }
+
+    fn get_number_of_possible_actions(&self) -> usize {
+        2
+    }
+
+    fn get_number_of_possible_states(&self) -> usize {
+        self.num_states
+    }
+
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        self.step_count
+    }
+
+    fn all_possible_states(&self) -> Vec<usize> {
+        (1..=self.num_states).collect()
+    }
+}
+
+impl ResetToState for RandomWalk {
+    fn reset_to_state(&mut self, state: &usize) {
+        self.position = *state;
+        self.step_count = 0;
+    }
+}
+
+impl Snapshot for RandomWalk {
+    fn snapshot(&self, _seed: u64) -> RandomWalk {
+        self.clone()
+    }
+}
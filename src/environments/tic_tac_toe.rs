@@ -0,0 +1,163 @@
+use serde::{ Deserialize, Serialize };
+
+use crate::environment::{ Environment, Snapshot };
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Mark {
+    Empty,
+    X,
+    O,
+}
+
+// rows, columns and diagonals of the board
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+/// Two player tic tac toe. X (player 0) moves first and players alternate placing their mark
+/// on an empty cell, numbered 0-8 row by row. The player completing a line gets a reward of 1.
+#[derive(Clone)]
+pub struct TicTacToe {
+    board: [Mark; 9],
+    winner: Option<Mark>,
+    step_count: usize,
+}
+
+impl TicTacToe {
+    pub fn new() -> TicTacToe {
+        TicTacToe {
+            board: [Mark::Empty; 9],
+            winner: None,
+            step_count: 0,
+        }
+    }
+
+    pub fn get_winner(&self) -> Option<Mark> {
+        self.winner
+    }
+
+    fn current_mark(&self) -> Mark {
+        if self.get_current_player() == 0 { Mark::X } else { Mark::O }
+    }
+
+    fn number_of_marks(&self) -> usize {
+        self.board
+            .iter()
+            .filter(|mark| **mark != Mark::Empty)
+            .count()
+    }
+
+    fn has_line(&self, mark: Mark) -> bool {
+        LINES.iter().any(|line| line.iter().all(|cell| self.board[*cell] == mark))
+    }
+}
+
+impl Default for TicTacToe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Environment for TicTacToe {
+    type State = [Mark; 9];
+    type Action = usize;
+
+    fn reset(&mut self) {
+        self.board = [Mark::Empty; 9];
+        self.winner = None;
+        self.step_count = 0;
+    }
+
+    fn step(&mut self, action: usize) -> f64 {
+        if self.board[action] != Mark::Empty {
+            panic!("Invalid action");
+        }
+        let mark = self.current_mark();
+        self.board[action] = mark;
+        self.step_count += 1;
+        if self.has_line(mark) {
+            self.winner = Some(mark);
+            return 1.0;
+        }
+        0.0
+    }
+
+    fn get_state(&self) -> [Mark; 9] {
+        self.board
+    }
+
+    fn get_actions(&self) -> Vec<usize> {
+        if self.is_terminal() {
+            return vec![];
+        }
+        (0..9).filter(|cell| self.board[*cell] == Mark::Empty).collect()
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.winner.is_some() || self.number_of_marks() == 9
+    }
+
+    fn get_number_of_possible_actions(&self) -> usize {
+        9
+    }
+
+    // every assignment of marks to cells, reachable or not
+    fn get_number_of_possible_states(&self) -> usize {
+        (3usize).pow(9)
+    }
+
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        self.step_count
+    }
+
+    fn all_possible_states(&self) -> Vec<[Mark; 9]> {
+        let marks = [Mark::Empty, Mark::X, Mark::O];
+        (0..self.get_number_of_possible_states())
+            .map(|mut index| {
+                let mut board = [Mark::Empty; 9];
+                for cell in board.iter_mut() {
+                    *cell = marks[index % 3];
+                    index /= 3;
+                }
+                board
+            })
+            .collect()
+    }
+
+    fn get_current_player(&self) -> usize {
+        self.number_of_marks() % 2
+    }
+}
+
+impl Snapshot for TicTacToe {
+    // the game has no hidden randomness
+    fn snapshot(&self, _seed: u64) -> TicTacToe {
+        self.clone()
+    }
+}
+
+impl std::fmt::Display for TicTacToe {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.board.chunks(3) {
+            let symbols: Vec<&str> = row
+                .iter()
+                .map(|mark| {
+                    match mark {
+                        Mark::Empty => ".",
+                        Mark::X => "X",
+                        Mark::O => "O",
+                    }
+                })
+                .collect();
+            writeln!(f, "{}", symbols.join(" "))?;
+        }
+        Ok(())
+    }
+}
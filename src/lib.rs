@@ -2,4 +2,7 @@ pub mod environments;
 pub mod selectors;
 pub mod agents;
 pub mod environment;
+pub mod dynamic_programming;
+pub mod seed;
 pub mod store;
+pub mod trainer;
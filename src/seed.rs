@@ -0,0 +1,39 @@
+/// Splits one experiment seed into independent seeds for runs and their components.
+///
+/// Child seeds are derived by hashing the parent seed with the child index (SplitMix64),
+/// so the seed of a run does not depend on how many runs or components came before it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeedStream {
+    seed: u64,
+}
+
+impl SeedStream {
+    pub fn new(seed: u64) -> SeedStream {
+        SeedStream {
+            seed,
+        }
+    }
+
+    ///
+    /// Get an independent stream, e.g. one per run or one per component of a run
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - u64 - index of the child stream
+    pub fn child(&self, index: u64) -> SeedStream {
+        SeedStream {
+            seed: split_mix(split_mix(self.seed) ^ index),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+fn split_mix(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9e3779b97f4a7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
@@ -1,64 +1,135 @@
-use rand::Rng;
+use rand::{ rngs::StdRng, Rng, SeedableRng };
 
-use crate::{ agents::selector::Selector, environment::Environment, store::Store };
+use crate::{
+    agents::{ agent::{ Action, State }, selector::Selector },
+    environment::Environment,
+    store::StateActionStore,
+};
 
 pub struct EpsilonGreedySelector {
     epsilon: f64,
+    random_ties: bool,
+    /// constant step size (alpha) of q estimates, sample averages are used when unset
+    step_size: Option<f64>,
+    rng: StdRng,
 }
 
 impl EpsilonGreedySelector {
     pub fn new(epsilon: f64) -> EpsilonGreedySelector {
         EpsilonGreedySelector {
             epsilon,
+            random_ties: false,
+            step_size: None,
+            rng: StdRng::from_entropy(),
         }
     }
+
+    ///
+    /// Create a selector whose exploration is reproducible
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon` - f64 - probability of selecting a random action
+    /// * `seed` - u64 - seed for exploration
+    pub fn with_seed(epsilon: f64, seed: u64) -> EpsilonGreedySelector {
+        EpsilonGreedySelector {
+            epsilon,
+            random_ties: false,
+            step_size: None,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Break ties between greedy actions uniformly at random instead of picking the last one,
+    /// which matters when many estimates are still at their initial value
+    pub fn with_random_ties(mut self) -> EpsilonGreedySelector {
+        self.random_ties = true;
+        self
+    }
+
+    ///
+    /// Move q estimates by a constant fraction of their error instead of averaging samples,
+    /// which weights recent rewards more and so tracks nonstationary problems
+    ///
+    /// # Arguments
+    ///
+    /// * `step_size` - f64 - step size (alpha) of the update
+    pub fn with_step_size(mut self, step_size: f64) -> EpsilonGreedySelector {
+        self.step_size = Some(step_size);
+        self
+    }
 }
 
 impl Selector for EpsilonGreedySelector {
-    fn select_action<T: Environment, S: Store>(
-        &self,
+    fn select_action<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &mut self,
         environment: &mut T,
         store: &S,
         _store_action_count: &S
-    ) -> usize {
-        let mut rng = rand::thread_rng();
+    ) -> T::Action {
+        let rng = &mut self.rng;
         let state = environment.get_state();
+        let mut actions = environment.get_actions();
         // generate random number between 0 and 1
         let random_number = rng.gen::<f64>();
-        let number_of_possible_actions = environment.get_number_of_possible_actions();
         if random_number < self.epsilon {
-            rng.gen_range(0..number_of_possible_actions)
+            let index = rng.gen_range(0..actions.len());
+            actions.swap_remove(index)
         } else {
-            let mut max: f64 = 0.0;
-            let mut max_index = 0;
-            for i in 0..number_of_possible_actions {
-                let id = store.generate_id(state.clone(), Some(i));
-                let current_q_estimate = store.get_float(&id);
-                if current_q_estimate >= max {
-                    max = current_q_estimate;
-                    max_index = i;
-                }
-            }
-            max_index
+            let greedy_indices = greedy_indices(store, &state, &actions);
+            let max_index = if self.random_ties {
+                greedy_indices[rng.gen_range(0..greedy_indices.len())]
+            } else {
+                *greedy_indices.last().unwrap()
+            };
+            actions.swap_remove(max_index)
         }
     }
 
-    fn get_new_q_estimate<T: Environment, S: Store>(
+    fn get_action_probabilities<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &self,
+        environment: &T,
+        store: &S,
+        _store_action_count: &S
+    ) -> Vec<(T::Action, f64)> {
+        let state = environment.get_state();
+        let actions = environment.get_actions();
+        let mut greedy_indices = greedy_indices(store, &state, &actions);
+        if !self.random_ties {
+            greedy_indices = greedy_indices.split_off(greedy_indices.len() - 1);
+        }
+        // every action can be picked while exploring, the greedy ones also while exploiting
+        let exploration_probability = self.epsilon / (actions.len() as f64);
+        let greedy_probability = (1.0 - self.epsilon) / (greedy_indices.len() as f64);
+        actions
+            .into_iter()
+            .enumerate()
+            .map(|(i, action)| {
+                if greedy_indices.contains(&i) {
+                    (action, exploration_probability + greedy_probability)
+                } else {
+                    (action, exploration_probability)
+                }
+            })
+            .collect()
+    }
+
+    fn get_new_q_estimate<T: Environment, S: StateActionStore<T::State, T::Action>>(
         &self,
         _environment: &mut T,
         store: &S,
         store_action_count: &S,
-        state: String,
-        action: usize,
+        state: T::State,
+        action: T::Action,
         reward: f64
     ) -> f64 {
-        let action_id = store_action_count.generate_id(state.clone(), Some(action));
-        let num_visits = store_action_count.get_float(&action_id);
         let id = store.generate_id(state, Some(action));
+        let step_size = match self.step_size {
+            Some(step_size) => step_size,
+            None => 1.0 / store_action_count.get_float(&id),
+        };
         let current_q_estimate = store.get_float(&id);
-        let new_q_estimate =
-            current_q_estimate + (1.0 / (num_visits as f64)) * (reward - current_q_estimate);
-        new_q_estimate
+        current_q_estimate + step_size * (reward - current_q_estimate)
     }
 
     ///
@@ -69,20 +140,40 @@ impl Selector for EpsilonGreedySelector {
     /// * `environment` - &mut T - environment
     /// * `store` - &S - store
     /// * `store_state_count` - &S - store that maps state to number of times state has been visited
-    /// * `state` - usize - state
-    fn get_new_value_estimate<T: Environment, S: Store>(
+    /// * `state` - T::State - state
+    fn get_new_value_estimate<T: Environment, S: StateActionStore<T::State, T::Action>>(
         &self,
-        environment: &mut T,
+        _environment: &mut T,
         store: &S,
         store_state_count: &S,
-        state: String,
+        state: T::State,
         reward: f64
     ) -> f64 {
         let id = store.generate_id(state, None);
         let current_value_estimate = store.get_float(&id);
-        let new_value_estimate =
-            current_value_estimate +
-            (1.0 / (store_state_count.get_float(&id) + 1.0)) * (reward - current_value_estimate);
-        new_value_estimate
+        current_value_estimate +
+            (1.0 / (store_state_count.get_float(&id) + 1.0)) * (reward - current_value_estimate)
+    }
+}
+
+/// Indices of the actions with the largest q estimate, in the order of the actions
+fn greedy_indices<St: State, A: Action, S: StateActionStore<St, A>>(
+    store: &S,
+    state: &St,
+    actions: &[A]
+) -> Vec<usize> {
+    let mut max: f64 = f64::NEG_INFINITY;
+    let mut max_indices = vec![];
+    for (i, action) in actions.iter().enumerate() {
+        let id = store.generate_id(state.clone(), Some(action.clone()));
+        let current_q_estimate = store.get_float(&id);
+        if current_q_estimate > max {
+            max = current_q_estimate;
+            max_indices.clear();
+        }
+        if current_q_estimate >= max {
+            max_indices.push(i);
+        }
     }
+    max_indices
 }
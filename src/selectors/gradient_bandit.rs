@@ -0,0 +1,220 @@
+use rand::{ rngs::StdRng, Rng, SeedableRng };
+
+use crate::{
+    agents::{ agent::{ Action, State }, selector::Selector },
+    environment::Environment,
+    store::StateActionStore,
+};
+
+/// Softmax over numerical action preferences learned by stochastic gradient ascent, as in
+/// Sutton & Barto section 2.8. The q store holds the preference of every state action pair,
+/// and the average reward used as a baseline occupies its state value slot `(state, None)`,
+/// so the q store must not also be used for state value estimates.
+pub struct GradientBanditSelector {
+    /// step size (alpha) of the preference updates
+    step_size: f64,
+    baseline: bool,
+    rng: StdRng,
+}
+
+impl GradientBanditSelector {
+    pub fn new(step_size: f64) -> GradientBanditSelector {
+        GradientBanditSelector {
+            step_size,
+            baseline: true,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    ///
+    /// Create a selector whose action sampling is reproducible
+    ///
+    /// # Arguments
+    ///
+    /// * `step_size` - f64 - step size (alpha) of the preference updates
+    /// * `seed` - u64 - seed for sampling actions
+    pub fn with_seed(step_size: f64, seed: u64) -> GradientBanditSelector {
+        GradientBanditSelector {
+            step_size,
+            baseline: true,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Compare rewards against 0 instead of the average reward, which slows learning
+    /// considerably when the rewards are far from 0
+    pub fn without_baseline(mut self) -> GradientBanditSelector {
+        self.baseline = false;
+        self
+    }
+
+    /// Average reward received in the state, including the reward being learned from,
+    /// read from the state value slot of the q store
+    fn updated_baseline<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &self,
+        environment: &T,
+        store: &S,
+        store_action_count: &S,
+        state: &T::State,
+        reward: f64
+    ) -> f64 {
+        let id = store.generate_id(state.clone(), None);
+        let num_rewards: f64 = environment
+            .get_actions()
+            .into_iter()
+            .map(|action| {
+                store_action_count.get_float(&store.generate_id(state.clone(), Some(action)))
+            })
+            .sum();
+        let average_reward = store.get_float(&id);
+        average_reward + (reward - average_reward) / num_rewards.max(1.0)
+    }
+}
+
+impl Selector for GradientBanditSelector {
+    fn select_action<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &mut self,
+        environment: &mut T,
+        store: &S,
+        _store_action_count: &S
+    ) -> T::Action {
+        let state = environment.get_state();
+        let mut actions = environment.get_actions();
+        let probabilities = softmax(store, &state, &actions);
+        // sample the action whose cumulative probability first exceeds a uniform number
+        let random_number = self.rng.gen::<f64>();
+        let mut cumulative_probability = 0.0;
+        let mut index = actions.len() - 1;
+        for (i, probability) in probabilities.iter().enumerate() {
+            cumulative_probability += probability;
+            if random_number < cumulative_probability {
+                index = i;
+                break;
+            }
+        }
+        actions.swap_remove(index)
+    }
+
+    fn get_action_probabilities<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &self,
+        environment: &T,
+        store: &S,
+        _store_action_count: &S
+    ) -> Vec<(T::Action, f64)> {
+        let state = environment.get_state();
+        let actions = environment.get_actions();
+        let probabilities = softmax(store, &state, &actions);
+        actions.into_iter().zip(probabilities).collect()
+    }
+
+    /// New preference of the action taken, the other preferences move as well
+    /// so agents should call `update_q_estimates`
+    fn get_new_q_estimate<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &self,
+        environment: &mut T,
+        store: &S,
+        store_action_count: &S,
+        state: T::State,
+        action: T::Action,
+        reward: f64
+    ) -> f64 {
+        let baseline = if self.baseline {
+            self.updated_baseline(environment, store, store_action_count, &state, reward)
+        } else {
+            0.0
+        };
+        let actions = environment.get_actions();
+        let probabilities = softmax(store, &state, &actions);
+        let index = actions
+            .iter()
+            .position(|a| *a == action)
+            .unwrap();
+        let id = store.generate_id(state, Some(action));
+        store.get_float(&id) + self.step_size * (reward - baseline) * (1.0 - probabilities[index])
+    }
+
+    ///
+    /// Move the preference of the action taken up when the reward beats the baseline and
+    /// every other preference down in proportion to its probability, and the other way around
+    /// when the reward falls short
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - &mut T - environment
+    /// * `store` - &mut S - store of preferences and average rewards
+    /// * `store_action_count` - &S - store that maps state action pairs to the number of times taken
+    /// * `state` - T::State - state the action was taken in
+    /// * `action` - T::Action - action taken
+    /// * `reward` - f64 - reward received
+    fn update_q_estimates<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &mut self,
+        environment: &mut T,
+        store: &mut S,
+        store_action_count: &S,
+        state: T::State,
+        action: T::Action,
+        reward: f64
+    ) {
+        let baseline = if self.baseline {
+            let average_reward = self.updated_baseline(
+                environment,
+                store,
+                store_action_count,
+                &state,
+                reward
+            );
+            store.store_float(store.generate_id(state.clone(), None), average_reward);
+            average_reward
+        } else {
+            0.0
+        };
+        let actions = environment.get_actions();
+        let probabilities = softmax(store, &state, &actions);
+        for (a, probability) in actions.into_iter().zip(probabilities) {
+            let indicator = if a == action { 1.0 } else { 0.0 };
+            let id = store.generate_id(state.clone(), Some(a));
+            let preference = store.get_float(&id);
+            store.store_float(
+                id,
+                preference + self.step_size * (reward - baseline) * (indicator - probability)
+            );
+        }
+    }
+
+    fn get_new_value_estimate<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &self,
+        _environment: &mut T,
+        store: &S,
+        store_state_count: &S,
+        state: T::State,
+        reward: f64
+    ) -> f64 {
+        let id = store.generate_id(state, None);
+        let current_value_estimate = store.get_float(&id);
+        current_value_estimate +
+            (1.0 / (store_state_count.get_float(&id) + 1.0)) * (reward - current_value_estimate)
+    }
+}
+
+/// Softmax of the preferences of the actions, in the order of the actions
+fn softmax<St: State, A: Action, S: StateActionStore<St, A>>(
+    store: &S,
+    state: &St,
+    actions: &[A]
+) -> Vec<f64> {
+    let preferences: Vec<f64> = actions
+        .iter()
+        .map(|action| store.get_float(&store.generate_id(state.clone(), Some(action.clone()))))
+        .collect();
+    // shifting by the largest preference leaves the probabilities unchanged and avoids overflow
+    let max_preference = preferences.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let exponentials: Vec<f64> = preferences
+        .iter()
+        .map(|preference| (preference - max_preference).exp())
+        .collect();
+    let total: f64 = exponentials.iter().sum();
+    exponentials
+        .iter()
+        .map(|exponential| exponential / total)
+        .collect()
+}
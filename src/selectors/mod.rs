@@ -1,2 +1,3 @@
 pub mod epsilon_greedy;
+pub mod gradient_bandit;
 pub mod ucb;
@@ -1,4 +1,4 @@
-use crate::{ agents::selector::Selector, environment::Environment, store::Store };
+use crate::{ agents::selector::Selector, environment::Environment, store::StateActionStore };
 
 pub struct UCBSelector {
     confidence_level: f64,
@@ -12,27 +12,27 @@ impl UCBSelector {
     }
 }
 
-impl Selector for UCBSelector {
-    fn select_action<T: Environment, S: Store>(
+impl UCBSelector {
+    /// Index of the action with the largest upper confidence bound, ties go to the last one
+    fn max_ucb_index<T: Environment, S: StateActionStore<T::State, T::Action>>(
         &self,
-        environment: &mut T,
+        environment: &T,
         store: &S,
-        store_action_count: &S
+        store_action_count: &S,
+        actions: &[T::Action]
     ) -> usize {
-        let mut max: f64 = 0.0;
+        let mut max: f64 = f64::NEG_INFINITY;
         let mut max_index = 0;
-        let num_actions = environment.get_number_of_possible_actions();
         let num_pulls = environment.get_total_number_of_actions_taken();
         let state = environment.get_state();
-        for i in 0..num_actions {
-            let id = store.generate_id(state.clone(), Some(i));
+        for (i, action) in actions.iter().enumerate() {
+            let id = store.generate_id(state.clone(), Some(action.clone()));
             let current_value_estimate = store.get_float(&id);
-            let action_count_id = store_action_count.generate_id(state.clone(), Some(i));
             // number of actions for state action pair
-            let state_action_count = store_action_count.get_float(&action_count_id);
+            let state_action_count = store_action_count.get_float(&id);
             let confidence = (
                 (self.confidence_level * (num_pulls as f64).ln()) /
-                (state_action_count as f64)
+                state_action_count
             ).sqrt();
 
             let ucb = current_value_estimate + confidence;
@@ -43,39 +43,63 @@ impl Selector for UCBSelector {
         }
         max_index
     }
+}
 
-    fn get_new_q_estimate<T: Environment, S: Store>(
+impl Selector for UCBSelector {
+    fn select_action<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &mut self,
+        environment: &mut T,
+        store: &S,
+        store_action_count: &S
+    ) -> T::Action {
+        let mut actions = environment.get_actions();
+        let max_index = self.max_ucb_index(environment, store, store_action_count, &actions);
+        actions.swap_remove(max_index)
+    }
+
+    /// UCB is deterministic, the action with the largest upper bound has probability 1
+    fn get_action_probabilities<T: Environment, S: StateActionStore<T::State, T::Action>>(
+        &self,
+        environment: &T,
+        store: &S,
+        store_action_count: &S
+    ) -> Vec<(T::Action, f64)> {
+        let actions = environment.get_actions();
+        let max_index = self.max_ucb_index(environment, store, store_action_count, &actions);
+        actions
+            .into_iter()
+            .enumerate()
+            .map(|(i, action)| (action, if i == max_index { 1.0 } else { 0.0 }))
+            .collect()
+    }
+
+    fn get_new_q_estimate<T: Environment, S: StateActionStore<T::State, T::Action>>(
         &self,
         _environment: &mut T,
         store: &S,
         store_action_count: &S,
-        state: String,
-        action: usize,
+        state: T::State,
+        action: T::Action,
         reward: f64
     ) -> f64 {
-        let action_id = store_action_count.generate_id(state.clone(), Some(action));
-        let num_visits = store_action_count.get_float(&action_id);
         let id = store.generate_id(state, Some(action));
+        let num_visits = store_action_count.get_float(&id);
         let current_action_value_estimate = store.get_float(&id);
-        let new_action_value_estimate =
-            current_action_value_estimate +
-            (1.0 / (num_visits as f64)) * (reward - current_action_value_estimate);
-        new_action_value_estimate
+        current_action_value_estimate +
+            (1.0 / num_visits) * (reward - current_action_value_estimate)
     }
 
-    fn get_new_value_estimate<T: Environment, S: Store>(
+    fn get_new_value_estimate<T: Environment, S: StateActionStore<T::State, T::Action>>(
         &self,
-        environment: &mut T,
+        _environment: &mut T,
         store: &S,
         store_state_count: &S,
-        state: String,
+        state: T::State,
         reward: f64
     ) -> f64 {
         let id = store.generate_id(state, None);
         let current_value_estimate = store.get_float(&id);
-        let new_value_estimate =
-            current_value_estimate +
-            (1.0 / (store_state_count.get_float(&id) + 1.0)) * (reward - current_value_estimate);
-        new_value_estimate
+        current_value_estimate +
+            (1.0 / (store_state_count.get_float(&id) + 1.0)) * (reward - current_value_estimate)
     }
 }
@@ -1,34 +1,286 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    fs::File,
+    hash::{ BuildHasherDefault, Hash },
+    io::{ self, BufReader, BufWriter },
+    path::{ Path, PathBuf },
+};
 
-pub trait Store {
-    fn generate_id(&self, state: String, action: Option<usize>) -> String {
-        match action {
-            Some(action) => format!("{}-{}", state, action),
-            None => format!("{}", state),
+use rustc_hash::FxHasher;
+use serde::{ de::DeserializeOwned, Serialize };
+
+use crate::{ agents::agent::{ Action, State }, environment::Environment };
+
+/// Key used to look up a value for a state or a state action pair
+pub type StoreId<S, A> = (S, Option<A>);
+
+type FxHashMap<K, V> = HashMap<K, V, BuildHasherDefault<FxHasher>>;
+
+/// File format used to persist a store
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StoreFormat {
+    /// human readable list of `[key, value]` entries
+    Json,
+    /// compact bincode encoding of the same entries
+    Binary,
+}
+
+impl StoreFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            StoreFormat::Json => "json",
+            StoreFormat::Binary => "bin",
         }
     }
-    fn store_float(&mut self, key: String, value: f64);
-    fn get_float(&self, key: &String) -> f64;
+}
+
+///
+/// Get the path of a named store inside a checkpoint directory
+///
+/// # Arguments
+///
+/// * `directory` - &Path - checkpoint directory
+/// * `name` - &str - name of the store, e.g. `q`
+/// * `format` - StoreFormat - format the store is written in
+pub fn store_path(directory: &Path, name: &str, format: StoreFormat) -> PathBuf {
+    directory.join(format!("{}.{}", name, format.extension()))
+}
+
+fn invalid_data<E: std::error::Error + Send + Sync + 'static>(error: E) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+/// Table of floats keyed by any hashable key. Missing keys read as 0.0.
+pub trait Store<K: Hash + Eq> {
+    fn store_float(&mut self, key: K, value: f64);
+    fn get_float(&self, key: &K) -> f64;
+    /// Add `amount` to the value stored under `key`
+    fn increment_float(&mut self, key: K, amount: f64) {
+        let value = self.get_float(&key);
+        self.store_float(key, value + amount);
+    }
+    /// All keys held by the store along with their values
+    fn entries(&self) -> Vec<(K, f64)>;
     fn new() -> Self;
+
+    ///
+    /// Write every entry of the store to a file
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - &Path - file to write, created or truncated
+    /// * `format` - StoreFormat - format to write
+    fn save(&self, path: &Path, format: StoreFormat) -> io::Result<()> where K: Serialize {
+        let writer = BufWriter::new(File::create(path)?);
+        let entries = self.entries();
+        match format {
+            StoreFormat::Json => serde_json::to_writer(writer, &entries).map_err(invalid_data),
+            StoreFormat::Binary => bincode::serialize_into(writer, &entries).map_err(invalid_data),
+        }
+    }
+
+    ///
+    /// Read a store previously written with `save`
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - &Path - file to read
+    /// * `format` - StoreFormat - format the file was written in
+    fn load(path: &Path, format: StoreFormat) -> io::Result<Self>
+        where K: DeserializeOwned, Self: Sized
+    {
+        let reader = BufReader::new(File::open(path)?);
+        let entries: Vec<(K, f64)> = match format {
+            StoreFormat::Json => serde_json::from_reader(reader).map_err(invalid_data)?,
+            StoreFormat::Binary => bincode::deserialize_from(reader).map_err(invalid_data)?,
+        };
+        let mut store = Self::new();
+        for (key, value) in entries {
+            store.store_float(key, value);
+        }
+        Ok(store)
+    }
 }
 
-pub struct MemoryStore {
-    store: HashMap<String, f64>,
+/// Store keyed by state or state action pairs, as used by agents and selectors
+pub trait StateActionStore<S: State, A: Action>: Store<StoreId<S, A>> {
+    fn generate_id(&self, state: S, action: Option<A>) -> StoreId<S, A> {
+        (state, action)
+    }
+}
+
+impl<S: State, A: Action, T: Store<StoreId<S, A>>> StateActionStore<S, A> for T {}
+
+/// Hash map backed store. Keys are hashed with FxHash, which is much cheaper than the
+/// default SipHash for the small integer-like keys used by tabular agents.
+pub struct MemoryStore<K: Hash + Eq> {
+    store: FxHashMap<K, f64>,
 }
 
-impl Store for MemoryStore {
-    fn store_float(&mut self, key: String, value: f64) {
+impl<K: Hash + Eq + Clone> Store<K> for MemoryStore<K> {
+    fn store_float(&mut self, key: K, value: f64) {
         self.store.insert(key, value);
     }
-    fn get_float(&self, key: &String) -> f64 {
+    fn get_float(&self, key: &K) -> f64 {
         match self.store.get(key) {
             Some(value) => *value,
             None => 0.0,
         }
     }
-    fn new() -> MemoryStore {
+    fn increment_float(&mut self, key: K, amount: f64) {
+        *self.store.entry(key).or_insert(0.0) += amount;
+    }
+    fn entries(&self) -> Vec<(K, f64)> {
+        self.store
+            .iter()
+            .map(|(key, value)| (key.clone(), *value))
+            .collect()
+    }
+    fn new() -> MemoryStore<K> {
         MemoryStore {
-            store: HashMap::new(),
+            store: HashMap::default(),
         }
     }
 }
+
+/// Array backed store for enumerable state spaces.
+///
+/// Every state is mapped to a contiguous row in a flat buffer. Slot 0 of a row holds the
+/// value stored for the state itself and slot `i + 1` holds the value for the i-th action,
+/// so a greedy scan over the actions of a state reads a single cache line.
+/// States and actions that were not known up front are appended on first write.
+pub struct DenseStore<S: State, A: Action> {
+    state_indices: FxHashMap<S, usize>,
+    action_indices: FxHashMap<A, usize>,
+    values: Vec<f64>,
+    row_length: usize,
+}
+
+impl<S: State, A: Action> DenseStore<S, A> {
+    ///
+    /// Create a store sized for the given states and actions
+    ///
+    /// # Arguments
+    ///
+    /// * `states` - Vec<S> - states to allocate rows for
+    /// * `actions` - Vec<A> - actions available in every state
+    pub fn with_states_and_actions(states: Vec<S>, actions: Vec<A>) -> DenseStore<S, A> {
+        let mut store = DenseStore {
+            state_indices: FxHashMap::default(),
+            action_indices: FxHashMap::default(),
+            values: Vec::new(),
+            row_length: 1,
+        };
+        for action in actions {
+            store.action_index_or_insert(action);
+        }
+        store.values.reserve(states.len() * store.row_length);
+        for state in states {
+            store.state_index_or_insert(state);
+        }
+        store
+    }
+
+    ///
+    /// Create a store with a row for every state of an environment and a slot for each action
+    /// available in its current state. Actions only available in other states widen every
+    /// row when they are first written.
+    ///
+    /// # Arguments
+    ///
+    /// * `environment` - &T - environment that enumerates its states and actions
+    pub fn from_environment<T: Environment<State = S, Action = A>>(
+        environment: &T
+    ) -> DenseStore<S, A> {
+        DenseStore::with_states_and_actions(
+            environment.all_possible_states(),
+            environment.get_actions()
+        )
+    }
+
+    pub fn get_number_of_states(&self) -> usize {
+        self.state_indices.len()
+    }
+
+    pub fn get_number_of_actions(&self) -> usize {
+        self.action_indices.len()
+    }
+
+    fn offset(&self, key: &StoreId<S, A>) -> Option<usize> {
+        let row = *self.state_indices.get(&key.0)?;
+        let column = match &key.1 {
+            Some(action) => *self.action_indices.get(action)? + 1,
+            None => 0,
+        };
+        Some(row * self.row_length + column)
+    }
+
+    fn offset_or_insert(&mut self, key: StoreId<S, A>) -> usize {
+        let column = match key.1 {
+            Some(action) => self.action_index_or_insert(action) + 1,
+            None => 0,
+        };
+        let row = self.state_index_or_insert(key.0);
+        row * self.row_length + column
+    }
+
+    fn state_index_or_insert(&mut self, state: S) -> usize {
+        let next_index = self.state_indices.len();
+        let index = *self.state_indices.entry(state).or_insert(next_index);
+        if index == next_index {
+            self.values.resize(self.values.len() + self.row_length, 0.0);
+        }
+        index
+    }
+
+    fn action_index_or_insert(&mut self, action: A) -> usize {
+        if let Some(index) = self.action_indices.get(&action) {
+            return *index;
+        }
+        let index = self.action_indices.len();
+        self.action_indices.insert(action, index);
+        // widen every row by one slot for the new action
+        let new_row_length = self.row_length + 1;
+        let mut values = vec![0.0; self.state_indices.len() * new_row_length];
+        for (row, chunk) in self.values.chunks(self.row_length).enumerate() {
+            values[row * new_row_length..row * new_row_length + self.row_length].copy_from_slice(
+                chunk
+            );
+        }
+        self.values = values;
+        self.row_length = new_row_length;
+        index
+    }
+}
+
+impl<S: State, A: Action> Store<StoreId<S, A>> for DenseStore<S, A> {
+    fn store_float(&mut self, key: StoreId<S, A>, value: f64) {
+        let offset = self.offset_or_insert(key);
+        self.values[offset] = value;
+    }
+    fn get_float(&self, key: &StoreId<S, A>) -> f64 {
+        match self.offset(key) {
+            Some(offset) => self.values[offset],
+            None => 0.0,
+        }
+    }
+    fn increment_float(&mut self, key: StoreId<S, A>, amount: f64) {
+        let offset = self.offset_or_insert(key);
+        self.values[offset] += amount;
+    }
+    fn entries(&self) -> Vec<(StoreId<S, A>, f64)> {
+        let mut entries = Vec::with_capacity(self.values.len());
+        for (state, row) in self.state_indices.iter() {
+            let offset = row * self.row_length;
+            entries.push(((state.clone(), None), self.values[offset]));
+            for (action, column) in self.action_indices.iter() {
+                let value = self.values[offset + column + 1];
+                entries.push(((state.clone(), Some(action.clone())), value));
+            }
+        }
+        entries
+    }
+    fn new() -> DenseStore<S, A> {
+        DenseStore::with_states_and_actions(Vec::new(), Vec::new())
+    }
+}
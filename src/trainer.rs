@@ -0,0 +1,196 @@
+use crate::agents::agent::Agent;
+
+/// How long a single run of an agent lasts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrainingBudget {
+    /// act for a fixed number of steps, resetting whenever an episode ends
+    Steps(usize),
+    /// act until the given number of episodes have ended. An environment that never terminates
+    /// never ends an episode, so cap its episodes with `Trainer::with_max_episode_length`
+    /// or truncate them in the agent
+    Episodes(usize),
+}
+
+/// Everything recorded during a single run of an agent
+#[derive(Debug, Clone, Default)]
+pub struct RunRecord {
+    /// reward received at every step
+    pub rewards: Vec<f64>,
+    /// sum of rewards of every completed or truncated episode
+    pub episode_returns: Vec<f64>,
+    /// whether the action taken at every step was optimal,
+    /// always false when the environment does not know its optimal actions
+    pub optimal_actions: Vec<bool>,
+    /// expected reward lost by the action taken at every step,
+    /// always zero when the environment does not know its optimal actions
+    pub regrets: Vec<f64>,
+}
+
+/// Records of independent runs of an agent
+#[derive(Debug, Clone, Default)]
+pub struct TrainingReport {
+    pub runs: Vec<RunRecord>,
+}
+
+impl TrainingReport {
+    /// Reward at every step averaged across runs
+    pub fn average_rewards(&self) -> Vec<f64> {
+        average_columns(self.runs.iter().map(|run| run.rewards.as_slice()))
+    }
+
+    /// Return of every episode averaged across runs
+    pub fn average_episode_returns(&self) -> Vec<f64> {
+        average_columns(self.runs.iter().map(|run| run.episode_returns.as_slice()))
+    }
+
+    /// Fraction of runs that took the optimal action at every step
+    pub fn optimal_action_rates(&self) -> Vec<f64> {
+        let optimal_actions: Vec<Vec<f64>> = self.runs
+            .iter()
+            .map(|run|
+                run.optimal_actions
+                    .iter()
+                    .map(|is_optimal| if *is_optimal { 1.0 } else { 0.0 })
+                    .collect()
+            )
+            .collect();
+        average_columns(optimal_actions.iter().map(|run| run.as_slice()))
+    }
+
+    /// Regret at every step averaged across runs
+    pub fn average_regrets(&self) -> Vec<f64> {
+        average_columns(self.runs.iter().map(|run| run.regrets.as_slice()))
+    }
+
+    /// Regret summed over all steps up to and including each step, averaged across runs
+    pub fn cumulative_regrets(&self) -> Vec<f64> {
+        let mut total = 0.0;
+        self.average_regrets()
+            .iter()
+            .map(|regret| {
+                total += regret;
+                total
+            })
+            .collect()
+    }
+}
+
+/// Average values at each index across runs. Runs of different lengths are averaged
+/// over the runs that reached the index.
+fn average_columns<'a, I: Iterator<Item = &'a [f64]>>(runs: I) -> Vec<f64> {
+    let mut totals: Vec<f64> = vec![];
+    let mut counts: Vec<usize> = vec![];
+    for run in runs {
+        if run.len() > totals.len() {
+            totals.resize(run.len(), 0.0);
+            counts.resize(run.len(), 0);
+        }
+        for (i, value) in run.iter().enumerate() {
+            totals[i] += value;
+            counts[i] += 1;
+        }
+    }
+    totals
+        .iter()
+        .zip(counts.iter())
+        .map(|(total, count)| total / (*count as f64))
+        .collect()
+}
+
+/// Drives the act/learn loop of an agent
+pub struct Trainer {
+    budget: TrainingBudget,
+    independent_runs: usize,
+    max_episode_length: Option<usize>,
+}
+
+impl Trainer {
+    ///
+    /// Create a new trainer
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - TrainingBudget - number of steps or episodes in each run
+    /// * `independent_runs` - usize - number of runs, each with a freshly created agent
+    pub fn new(budget: TrainingBudget, independent_runs: usize) -> Trainer {
+        Trainer {
+            budget,
+            independent_runs,
+            max_episode_length: None,
+        }
+    }
+
+    ///
+    /// Cut off every episode that reaches the given number of steps. The agent is reset without
+    /// being told, so agents that learn once an episode ends discard it; agents that should
+    /// bootstrap from the state an episode stopped in need their own truncation instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_episode_length` - usize - number of steps after which an episode is cut off
+    pub fn with_max_episode_length(mut self, max_episode_length: usize) -> Trainer {
+        assert!(max_episode_length > 0, "episodes must be allowed at least one step");
+        self.max_episode_length = Some(max_episode_length);
+        self
+    }
+
+    ///
+    /// Train a fresh agent for every independent run
+    ///
+    /// # Arguments
+    ///
+    /// * `create_agent` - F - called with the run index to create the agent for that run
+    pub fn run<A: Agent, F: FnMut(usize) -> A>(&self, mut create_agent: F) -> TrainingReport {
+        let mut report = TrainingReport::default();
+        for r in 0..self.independent_runs {
+            let mut agent = create_agent(r);
+            report.runs.push(self.train(&mut agent));
+        }
+        report
+    }
+
+    ///
+    /// Train a single agent for one run of the budget
+    ///
+    /// # Arguments
+    ///
+    /// * `agent` - &mut A - agent to train, its estimates are kept after the run
+    pub fn train<A: Agent>(&self, agent: &mut A) -> RunRecord {
+        let mut record = RunRecord::default();
+        let mut episode_return = 0.0;
+        let mut episode_length = 0;
+        loop {
+            let done = match self.budget {
+                TrainingBudget::Steps(num_steps) => record.rewards.len() >= num_steps,
+                TrainingBudget::Episodes(num_episodes) => {
+                    record.episode_returns.len() >= num_episodes
+                }
+            };
+            if done {
+                break;
+            }
+            let state = agent.get_state();
+            let action = agent.select_action();
+            // judged before acting, as the true values of a nonstationary environment move
+            let is_optimal = agent.is_optimal_action(&action).unwrap_or(false);
+            let regret = agent.regret(&action).unwrap_or(0.0);
+            let reward = agent.take_action(action.clone());
+            let is_terminal = agent.is_terminal();
+            agent.update_estimate(state, action, reward, is_terminal);
+            record.rewards.push(reward);
+            record.optimal_actions.push(is_optimal);
+            record.regrets.push(regret);
+            episode_return += reward;
+            episode_length += 1;
+            let is_cut_off = self.max_episode_length.is_some_and(|max| episode_length >= max);
+            // truncated episodes end like terminated ones, only the agent is told the difference
+            if is_terminal || agent.is_truncated() || is_cut_off {
+                record.episode_returns.push(episode_return);
+                episode_return = 0.0;
+                episode_length = 0;
+                agent.reset();
+            }
+        }
+        record
+    }
+}
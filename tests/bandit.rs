@@ -0,0 +1,59 @@
+use rl_examples::environments::bandit::{ Bandit, BanditType, KArmedBandit };
+
+#[test]
+fn uniform_bandit_with_equal_bounds_pays_a_constant() {
+    let mut bandit = Bandit::new("constant".to_string(), BanditType::Uniform { low: 2.0, high: 2.0 });
+    for _ in 0..10 {
+        assert_eq!(bandit.pull(), 2.0);
+    }
+}
+
+#[test]
+#[should_panic(expected = "low must not be greater than high")]
+fn uniform_bandit_rejects_inverted_bounds() {
+    Bandit::new("inverted".to_string(), BanditType::Uniform { low: 1.0, high: 0.0 });
+}
+
+#[test]
+#[should_panic(expected = "probability must be between 0 and 1")]
+fn bernoulli_bandit_rejects_probability_above_one() {
+    Bandit::new("invalid".to_string(), BanditType::Bernoulli { probability: 1.5 });
+}
+
+#[test]
+#[should_panic(expected = "rate must be positive")]
+fn exponential_bandit_rejects_zero_rate() {
+    Bandit::new("invalid".to_string(), BanditType::Exponential { rate: 0.0 });
+}
+
+#[test]
+#[should_panic(expected = "standard deviation must not be negative")]
+fn normal_bandit_rejects_negative_standard_deviation() {
+    Bandit::new(
+        "invalid".to_string(),
+        BanditType::Normal { mean: 0.0, standard_deviation: -1.0 }
+    );
+}
+
+#[test]
+fn random_walk_starts_from_the_values_of_the_arms() {
+    let arms = vec![
+        BanditType::Bernoulli { probability: 0.3 },
+        BanditType::Normal { mean: 5.0, standard_deviation: 1.0 },
+    ];
+    let mut bandit = KArmedBandit::from_arms_with_seed(arms, 0).with_random_walk(0.01);
+    assert_eq!(bandit.get_value_by_index(0), 0.3);
+    assert_eq!(bandit.get_value_by_index(1), 5.0);
+    for _ in 0..100 {
+        bandit.pull_by_index(0);
+    }
+    // a hundred steps of 0.01 drift by about 0.1
+    assert_ne!(bandit.get_value_by_index(1), 5.0);
+    assert!((bandit.get_value_by_index(1) - 5.0).abs() < 1.0);
+}
+
+#[test]
+#[should_panic(expected = "random walk standard deviation must not be negative")]
+fn random_walk_rejects_negative_standard_deviation() {
+    KArmedBandit::with_seed(2, 0).with_random_walk(-0.01);
+}
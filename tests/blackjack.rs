@@ -0,0 +1,48 @@
+use rl_examples::{
+    environment::{ Environment, ResetToState },
+    environments::blackjack::{ BlackJackPlayer, Blackjack, BlackjackAction, BlackjackState },
+};
+
+#[test]
+fn ten_valued_cards_are_drawn_with_probability_four_thirteenths() {
+    let mut blackjack = Blackjack::with_seed(0);
+    let deals = 100000;
+    let mut tens = 0;
+    for _ in 0..deals {
+        blackjack.reset();
+        if blackjack.get_state().dealer_showing == 10 {
+            tens += 1;
+        }
+    }
+    // the standard error of the frequency is about 0.0015
+    let frequency = (tens as f64) / (deals as f64);
+    assert!((frequency - 4.0 / 13.0).abs() < 0.01, "frequency of tens was {}", frequency);
+}
+
+#[test]
+fn reset_never_deals_the_player_less_than_12() {
+    let mut blackjack = Blackjack::with_seed(1);
+    for _ in 0..10000 {
+        blackjack.reset();
+        let state = blackjack.get_state();
+        assert!((12..=21).contains(&state.player_sum), "player sum was {}", state.player_sum);
+    }
+}
+
+#[test]
+fn sticking_below_17_wins_when_the_dealer_busts() {
+    let mut blackjack = Blackjack::with_seed(2);
+    let state = BlackjackState { player_sum: 13, dealer_showing: 6, usable_ace: false };
+    let mut dealer_busts = 0;
+    for _ in 0..1000 {
+        blackjack.reset_to_state(&state);
+        // the dealer plays out its hand within the same step
+        let reward = blackjack.step(BlackjackAction::Stick);
+        assert!(blackjack.is_terminal());
+        if blackjack.dealer.did_bust() {
+            dealer_busts += 1;
+            assert_eq!(reward, 1.0);
+        }
+    }
+    assert!(dealer_busts > 0);
+}
@@ -0,0 +1,100 @@
+use rl_examples::{
+    agents::{ expected_sarsa::AgentExpectedSarsa, q_learning::AgentQLearning, sarsa::AgentSarsa },
+    environment::Environment,
+    environments::gridworld::{ GridAction, GridPosition, Gridworld },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+const EPSILON: f64 = 0.1;
+const STEP_SIZE: f64 = 0.1;
+const NUM_EPISODES: usize = 1000;
+
+// the optimal path walks right along the edge of the cliff
+const OPTIMAL_PATH_LENGTH: usize = 13;
+
+/// Greedy path from the start of cliff walking, given action value estimates
+fn greedy_path<Q: Fn(GridPosition, GridAction) -> f64>(q_estimate: Q) -> Vec<GridPosition> {
+    let gridworld = Gridworld::cliff_walking();
+    let actions = gridworld.get_actions();
+    gridworld.follow_policy(
+        |state| {
+            *actions
+                .iter()
+                .max_by(|a, b| q_estimate(*state, **a).total_cmp(&q_estimate(*state, **b)))
+                .unwrap()
+        },
+        100
+    )
+}
+
+/// Whether a path reaches the goal and keeps a row between itself and the middle of the cliff
+fn is_safe(path: &[GridPosition]) -> bool {
+    let goal = (3, 11);
+    *path.last().unwrap() == goal &&
+        path.iter().all(|(row, column)| *row < 2 || !(2..=9).contains(column))
+}
+
+fn train_sarsa(seed: u64) -> Vec<GridPosition> {
+    let mut agent = AgentSarsa::new(
+        Gridworld::cliff_walking(),
+        EpsilonGreedySelector::with_seed(EPSILON, seed).with_random_ties(),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        STEP_SIZE,
+        1.0
+    );
+    Trainer::new(TrainingBudget::Episodes(NUM_EPISODES), 1).train(&mut agent);
+    greedy_path(|state, action| agent.get_q_estimate(state, action))
+}
+
+fn train_expected_sarsa(seed: u64) -> Vec<GridPosition> {
+    let mut agent = AgentExpectedSarsa::new(
+        Gridworld::cliff_walking(),
+        EpsilonGreedySelector::with_seed(EPSILON, seed).with_random_ties(),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        STEP_SIZE,
+        1.0
+    );
+    Trainer::new(TrainingBudget::Episodes(NUM_EPISODES), 1).train(&mut agent);
+    greedy_path(|state, action| agent.get_q_estimate(state, action))
+}
+
+fn train_q_learning(seed: u64) -> Vec<GridPosition> {
+    let mut agent = AgentQLearning::new(
+        Gridworld::cliff_walking(),
+        EpsilonGreedySelector::with_seed(EPSILON, seed).with_random_ties(),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        STEP_SIZE,
+        1.0
+    );
+    Trainer::new(TrainingBudget::Episodes(NUM_EPISODES), 1).train(&mut agent);
+    greedy_path(|state, action| agent.get_q_estimate(state, action))
+}
+
+#[test]
+fn sarsa_learns_the_safe_path() {
+    for seed in 0..5 {
+        let path = train_sarsa(seed);
+        assert!(is_safe(&path), "seed {}: {:?}", seed, path);
+    }
+}
+
+#[test]
+fn expected_sarsa_learns_the_safe_path() {
+    for seed in 0..5 {
+        let path = train_expected_sarsa(seed);
+        assert!(is_safe(&path), "seed {}: {:?}", seed, path);
+    }
+}
+
+#[test]
+fn q_learning_learns_the_optimal_path() {
+    for seed in 0..5 {
+        let path = train_q_learning(seed);
+        assert_eq!(path.len() - 1, OPTIMAL_PATH_LENGTH, "seed {}: {:?}", seed, path);
+    }
+}
@@ -0,0 +1,61 @@
+use rl_examples::{
+    agents::{ agent::Agent, double_q_learning::AgentDoubleQLearning, q_learning::AgentQLearning },
+    environments::maximization_bias::MaximizationBias,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+const RUNS: usize = 200;
+const EPISODES: usize = 300;
+const EPSILON: f64 = 0.1;
+const STEP_SIZE: f64 = 0.1;
+const NUM_B_ACTIONS: usize = 10;
+
+/// Fraction of all episodes of all runs that moved left from A
+fn left_action_rate<A: Agent, F: Fn(SeedStream) -> A>(create_agent: F) -> f64 {
+    let trainer = Trainer::new(TrainingBudget::Episodes(1), 1);
+    let mut lefts = 0;
+    for r in 0..RUNS {
+        let mut agent = create_agent(SeedStream::new(0).child(r as u64));
+        for _ in 0..EPISODES {
+            // every episode starts in A, where moving left is the only sub-optimal action
+            if !trainer.train(&mut agent).optimal_actions[0] {
+                lefts += 1;
+            }
+        }
+    }
+    (lefts as f64) / ((RUNS * EPISODES) as f64)
+}
+
+#[test]
+fn double_q_learning_moves_left_from_a_less_often_than_q_learning() {
+    let q_learning = left_action_rate(|seeds| {
+        AgentQLearning::new(
+            MaximizationBias::with_seed(NUM_B_ACTIONS, seeds.child(0).seed()),
+            EpsilonGreedySelector::with_seed(EPSILON, seeds.child(1).seed()),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            STEP_SIZE,
+            1.0
+        )
+    });
+    let double_q_learning = left_action_rate(|seeds| {
+        AgentDoubleQLearning::with_seed(
+            MaximizationBias::with_seed(NUM_B_ACTIONS, seeds.child(0).seed()),
+            EpsilonGreedySelector::with_seed(EPSILON, seeds.child(1).seed()),
+            [MemoryStore::new(), MemoryStore::new()],
+            MemoryStore::new(),
+            STEP_SIZE,
+            1.0,
+            seeds.child(2).seed()
+        )
+    });
+    assert!(
+        double_q_learning < q_learning,
+        "Double Q-learning moved left {:.3} of the time, Q-learning {:.3}",
+        double_q_learning,
+        q_learning
+    );
+}
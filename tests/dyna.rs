@@ -0,0 +1,118 @@
+use rl_examples::{
+    agents::{
+        agent::Agent,
+        dyna_q::{ AgentDynaQ, DynaConfig },
+        prioritized_sweeping::{ AgentPrioritizedSweeping, PrioritizedSweepingConfig },
+    },
+    environments::gridworld::{ GridAction, GridPosition, Gridworld },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+const EPSILON: f64 = 0.1;
+
+// independent runs whose episodes are added up
+const RUNS: u64 = 10;
+
+// episodes after which an agent that has not taken a short episode is given up on
+const MAX_EPISODES: usize = 100;
+
+/// Number of episodes an agent trains for before one of them takes at most twice the length
+/// of a shortest path
+fn episodes_until_short_episode<
+    A: Agent<State = GridPosition, Action = GridAction, Environment = Gridworld>
+>(agent: &mut A) -> usize {
+    let trainer = Trainer::new(TrainingBudget::Episodes(1), 1);
+    let shortest_path_length = agent.get_environment().shortest_path_length().unwrap();
+    for episode in 1..=MAX_EPISODES {
+        if trainer.train(agent).rewards.len() <= 2 * shortest_path_length {
+            return episode;
+        }
+    }
+    MAX_EPISODES
+}
+
+fn dyna_q_episodes(planning_steps: usize) -> usize {
+    (0..RUNS)
+        .map(|seed| {
+            let mut agent = AgentDynaQ::with_seed(
+                Gridworld::dyna_maze(),
+                EpsilonGreedySelector::with_seed(EPSILON, seed).with_random_ties(),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                DynaConfig { planning_steps, ..DynaConfig::default() },
+                seed
+            );
+            episodes_until_short_episode(&mut agent)
+        })
+        .sum()
+}
+
+fn prioritized_sweeping_episodes(planning_steps: usize) -> usize {
+    (0..RUNS)
+        .map(|seed| {
+            let mut agent = AgentPrioritizedSweeping::new(
+                Gridworld::dyna_maze(),
+                EpsilonGreedySelector::with_seed(EPSILON, seed).with_random_ties(),
+                MemoryStore::new(),
+                MemoryStore::new(),
+                PrioritizedSweepingConfig { planning_steps, ..PrioritizedSweepingConfig::default() }
+            );
+            episodes_until_short_episode(&mut agent)
+        })
+        .sum()
+}
+
+#[test]
+fn dyna_q_learns_the_maze_in_fewer_episodes_with_more_planning() {
+    let episodes: Vec<usize> = [0, 5, 50]
+        .into_iter()
+        .map(dyna_q_episodes)
+        .collect();
+    assert!(episodes[0] > episodes[1] && episodes[1] > episodes[2], "{:?}", episodes);
+}
+
+#[test]
+fn prioritized_sweeping_learns_the_maze_in_fewer_episodes_with_more_planning() {
+    let episodes: Vec<usize> = [5, 50]
+        .into_iter()
+        .map(prioritized_sweeping_episodes)
+        .collect();
+    assert!(episodes[0] > episodes[1], "{:?}", episodes);
+}
+
+#[test]
+fn dyna_q_plus_finds_the_shortcut() {
+    let change_at_step = 3000;
+    for seed in 0..3 {
+        let mut agent = AgentDynaQ::with_seed(
+            Gridworld::shortcut_maze(change_at_step),
+            EpsilonGreedySelector::with_seed(EPSILON, seed).with_random_ties(),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            DynaConfig {
+                planning_steps: 50,
+                step_size: 1.0,
+                discount: 0.95,
+                exploration_bonus: Some(1e-3),
+            },
+            seed
+        );
+        let record = Trainer::new(TrainingBudget::Steps(2 * change_at_step), 1).train(&mut agent);
+        // the goal pays the only reward, so the rewards of the last steps count the episodes
+        // they finished, which take longer than the old shortest path unless the shortcut is used
+        let last_steps = 1000;
+        let episodes: f64 = record.rewards[record.rewards.len() - last_steps..].iter().sum();
+        let old_shortest_path_length = Gridworld::shortcut_maze(change_at_step)
+            .shortest_path_length()
+            .unwrap();
+        assert!(
+            (last_steps as f64) / episodes < (old_shortest_path_length as f64),
+            "seed {}: {} episodes in the last {} steps",
+            seed,
+            episodes,
+            last_steps
+        );
+    }
+}
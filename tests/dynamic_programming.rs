@@ -0,0 +1,122 @@
+use rl_examples::{
+    dynamic_programming::{
+        equiprobable_policy,
+        evaluate_policy,
+        policy_iteration,
+        value_iteration,
+        DpConfig,
+        DpSolution,
+        SweepMode,
+    },
+    environments::gridworld::{ GridAction, GridPosition, Gridworld },
+    store::{ MemoryStore, Store, StoreId },
+};
+
+type GridStore = MemoryStore<StoreId<GridPosition, GridAction>>;
+
+// values of the equiprobable random policy, Sutton & Barto Figure 4.1
+const EQUIPROBABLE_VALUES: [[f64; 4]; 4] = [
+    [0.0, -14.0, -20.0, -22.0],
+    [-14.0, -18.0, -20.0, -20.0],
+    [-20.0, -20.0, -18.0, -14.0],
+    [-22.0, -20.0, -14.0, 0.0],
+];
+
+const TOLERANCE: f64 = 1e-6;
+
+fn config(sweep_mode: SweepMode) -> DpConfig {
+    DpConfig { sweep_mode, ..DpConfig::default() }
+}
+
+fn value_grid(values: &GridStore) -> Vec<Vec<f64>> {
+    (0..4)
+        .map(|row| {
+            (0..4).map(|column| values.get_float(&((row, column), None))).collect()
+        })
+        .collect()
+}
+
+fn assert_close(values: &[Vec<f64>], expected: &[Vec<f64>]) {
+    for (row, (actual_row, expected_row)) in values.iter().zip(expected).enumerate() {
+        for (column, (actual, expected)) in actual_row.iter().zip(expected_row).enumerate() {
+            assert!(
+                (actual - expected).abs() < TOLERANCE,
+                "value of {:?} is {}, expected {}",
+                (row, column),
+                actual,
+                expected
+            );
+        }
+    }
+}
+
+/// Values of the optimal policy, one reward of -1 for every step to the nearest terminal corner
+fn optimal_values() -> Vec<Vec<f64>> {
+    (0..4)
+        .map(|row: usize| {
+            (0..4)
+                .map(|column: usize| -((row + column).min(6 - row - column) as f64))
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn evaluation_of_the_equiprobable_policy_matches_figure_4_1() {
+    let gridworld = Gridworld::dp_gridworld();
+    let expected: Vec<Vec<f64>> = EQUIPROBABLE_VALUES.iter()
+        .map(|row| row.to_vec())
+        .collect();
+    for sweep_mode in [SweepMode::Synchronous, SweepMode::InPlace] {
+        let (values, _): (GridStore, usize) = evaluate_policy(
+            &gridworld,
+            equiprobable_policy(&gridworld),
+            config(sweep_mode)
+        );
+        assert_close(&value_grid(&values), &expected);
+    }
+}
+
+#[test]
+fn policy_iteration_and_value_iteration_find_the_optimal_values() {
+    let gridworld = Gridworld::dp_gridworld();
+    for sweep_mode in [SweepMode::Synchronous, SweepMode::InPlace] {
+        let policy_solution: DpSolution<_, _, GridStore> = policy_iteration(
+            &gridworld,
+            config(sweep_mode)
+        );
+        let value_solution: DpSolution<_, _, GridStore> = value_iteration(
+            &gridworld,
+            config(sweep_mode)
+        );
+        assert_close(&value_grid(&policy_solution.values), &optimal_values());
+        assert_close(&value_grid(&value_solution.values), &optimal_values());
+        // following either policy from the start corner takes the three steps to a terminal one
+        for solution in [policy_solution, value_solution] {
+            let path = gridworld.follow_policy(|state| solution.policy[state], 10);
+            assert_eq!(path.len() - 1, 3, "{:?}", path);
+        }
+    }
+}
+
+#[test]
+fn synchronous_and_in_place_sweeps_reach_the_same_fixed_point() {
+    let gridworld = Gridworld::dp_gridworld();
+    let (synchronous, synchronous_sweeps): (GridStore, usize) = evaluate_policy(
+        &gridworld,
+        equiprobable_policy(&gridworld),
+        config(SweepMode::Synchronous)
+    );
+    let (in_place, in_place_sweeps): (GridStore, usize) = evaluate_policy(
+        &gridworld,
+        equiprobable_policy(&gridworld),
+        config(SweepMode::InPlace)
+    );
+    assert_close(&value_grid(&in_place), &value_grid(&synchronous));
+    assert!(
+        in_place_sweeps < synchronous_sweeps,
+        "in place took {} sweeps, synchronous {}",
+        in_place_sweeps,
+        synchronous_sweeps
+    );
+}
@@ -0,0 +1,93 @@
+use rl_examples::{
+    agents::selector::Selector,
+    environments::bandit::{ BanditType, KArmedBandit },
+    selectors::gradient_bandit::GradientBanditSelector,
+    store::{ MemoryStore, Store, StoreId },
+};
+
+type BanditStore = MemoryStore<StoreId<usize, usize>>;
+
+const STEP_SIZE: f64 = 0.1;
+
+/// Bandit whose arms pay 2 and 0
+fn constant_bandit() -> KArmedBandit {
+    KArmedBandit::from_arms_with_seed(
+        vec![
+            BanditType::Uniform { low: 2.0, high: 2.0 },
+            BanditType::Uniform { low: 0.0, high: 0.0 }
+        ],
+        0
+    )
+}
+
+///
+/// Learn from pulling an arm once each arm has been pulled once before, with an average
+/// reward of 1 so far
+///
+/// # Arguments
+///
+/// * `arm` - usize - arm pulled
+/// * `reward` - f64 - reward received
+fn learn_from_pull(arm: usize, reward: f64) -> BanditStore {
+    let mut bandit = constant_bandit();
+    let mut selector = GradientBanditSelector::with_seed(STEP_SIZE, 0);
+    let mut store = BanditStore::new();
+    let mut store_action_count = BanditStore::new();
+    store.store_float((0, None), 1.0);
+    // both earlier pulls and the one being learned from
+    store_action_count.store_float((0, Some(arm)), 2.0);
+    store_action_count.store_float((0, Some(1 - arm)), 1.0);
+    selector.update_q_estimates(&mut bandit, &mut store, &store_action_count, 0, arm, reward);
+    store
+}
+
+#[test]
+fn reward_above_the_baseline_moves_preference_toward_the_arm() {
+    let store = learn_from_pull(0, 2.0);
+    // the baseline occupies the state value slot and includes the reward learned from
+    let baseline = store.get_float(&(0, None));
+    assert!((baseline - 4.0 / 3.0).abs() < 1e-12, "{}", baseline);
+    assert!(store.get_float(&(0, Some(0))) > 0.0);
+    assert!(store.get_float(&(0, Some(1))) < 0.0);
+}
+
+#[test]
+fn reward_below_the_baseline_moves_preference_away_from_the_arm() {
+    let store = learn_from_pull(1, 0.0);
+    assert!(store.get_float(&(0, Some(1))) < 0.0);
+    assert!(store.get_float(&(0, Some(0))) > 0.0);
+}
+
+#[test]
+fn reward_without_baseline_moves_preference_toward_any_positive_reward() {
+    let mut bandit = constant_bandit();
+    let mut selector = GradientBanditSelector::with_seed(STEP_SIZE, 0).without_baseline();
+    let mut store = BanditStore::new();
+    let mut store_action_count = BanditStore::new();
+    store_action_count.store_float((0, Some(1)), 1.0);
+    selector.update_q_estimates(&mut bandit, &mut store, &store_action_count, 0, 1, 0.5);
+    assert!(store.get_float(&(0, Some(1))) > 0.0);
+    assert_eq!(store.get_float(&(0, None)), 0.0);
+}
+
+#[test]
+fn action_probabilities_sum_to_one() {
+    let bandit = constant_bandit();
+    let selector = GradientBanditSelector::with_seed(STEP_SIZE, 0);
+    let store_action_count = BanditStore::new();
+    // large preferences must not overflow the exponentials
+    for preferences in [[0.0, 0.0], [1.0, -2.0], [1000.0, 999.0]] {
+        let mut store = BanditStore::new();
+        for (arm, preference) in preferences.iter().enumerate() {
+            store.store_float((0, Some(arm)), *preference);
+        }
+        let probabilities = selector.get_action_probabilities(&bandit, &store, &store_action_count);
+        let total: f64 = probabilities
+            .iter()
+            .map(|(_, probability)| probability)
+            .sum();
+        assert!((total - 1.0).abs() < 1e-12, "{:?}", probabilities);
+        // the arm with the larger preference is more likely
+        assert!(probabilities[0].1 >= probabilities[1].1, "{:?}", probabilities);
+    }
+}
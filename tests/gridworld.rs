@@ -0,0 +1,138 @@
+use rl_examples::{
+    environment::{ Environment, Model },
+    environments::gridworld::{
+        GridAction,
+        GridCell,
+        GridParseError,
+        Gridworld,
+        GridworldConfig,
+        MoveSet,
+        WallChange,
+    },
+};
+
+#[test]
+fn parse_reads_cells_and_start() {
+    let gridworld = Gridworld::parse(
+        "
+        S.#
+        C1G
+        ",
+        GridworldConfig::default()
+    ).unwrap();
+    assert_eq!(gridworld.get_rows(), 2);
+    assert_eq!(gridworld.get_columns(), 3);
+    assert_eq!(gridworld.get_start(), (0, 0));
+    assert_eq!(gridworld.get_cell((0, 2)), GridCell::Wall);
+    assert_eq!(gridworld.get_cell((1, 0)), GridCell::Cliff);
+    assert_eq!(gridworld.get_cell((1, 1)), GridCell::Wind(1));
+    assert_eq!(gridworld.get_cell((1, 2)), GridCell::Goal);
+}
+
+#[test]
+fn parse_rejects_invalid_maps() {
+    let config = GridworldConfig::default();
+    assert_eq!(Gridworld::parse("", config).err(), Some(GridParseError::Empty));
+    assert_eq!(Gridworld::parse("S..\n..", config).err(), Some(GridParseError::UnevenRow(1)));
+    assert_eq!(Gridworld::parse("S.?", config).err(), Some(GridParseError::UnknownCell('?')));
+    assert_eq!(Gridworld::parse("...", config).err(), Some(GridParseError::MissingStart));
+    assert_eq!(Gridworld::parse("S.S", config).err(), Some(GridParseError::MultipleStarts));
+    assert_eq!(Gridworld::parse("Sa.", config).err(), Some(GridParseError::UnpairedTeleport('a')));
+}
+
+#[test]
+fn blocked_move_on_teleport_exit_stays_in_place() {
+    let mut gridworld = Gridworld::parse("Sa.a\n...G", GridworldConfig::default()).unwrap();
+    gridworld.step(GridAction::Right);
+    assert_eq!(gridworld.get_state(), (0, 3));
+    // bumping into the edge of the grid must not teleport the agent back
+    gridworld.step(GridAction::Right);
+    assert_eq!(gridworld.get_state(), (0, 3));
+    gridworld.step(GridAction::Up);
+    assert_eq!(gridworld.get_state(), (0, 3));
+
+    let transitions = gridworld.transitions(&(0, 3), &GridAction::Right);
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(transitions[0].next_state, (0, 3));
+    // leaving the exit and stepping back onto it still teleports
+    let transitions = gridworld.transitions(&(0, 2), &GridAction::Right);
+    assert_eq!(transitions[0].next_state, (0, 1));
+}
+
+#[test]
+fn wind_pushes_moves_up_until_the_edge() {
+    let mut gridworld = Gridworld::windy_gridworld(MoveSet::Cardinal, false);
+    // from the start, walking right along row 3 into the windy columns
+    for _ in 0..4 {
+        gridworld.step(GridAction::Right);
+    }
+    assert_eq!(gridworld.get_state(), (2, 4));
+}
+
+#[test]
+fn dyna_maze_matches_the_maze_layout() {
+    let gridworld = Gridworld::dyna_maze();
+    assert_eq!(
+        gridworld.render(),
+        "\
+.......#G
+..#....#.
+@.#....#.
+..#......
+.....#...
+.........
+"
+    );
+}
+
+#[test]
+fn wall_change_moves_the_agent_out_of_a_new_wall() {
+    let mut gridworld = Gridworld::parse(
+        "
+        S..
+        ...
+        ..G
+        ",
+        GridworldConfig::default()
+    )
+        .unwrap()
+        .with_wall_change(WallChange {
+            at_step: 1,
+            walls: vec![(0, 1), (0, 2), (1, 1), (1, 2)],
+        });
+    gridworld.step(GridAction::Right);
+    // the agent stood on (0, 1) when it became a wall, and (0, 0) is the only open neighbour
+    assert_eq!(gridworld.get_state(), (0, 0));
+    assert_eq!(gridworld.get_cell((1, 1)), GridCell::Wall);
+    gridworld.step(GridAction::Down);
+    assert_eq!(gridworld.get_state(), (1, 0));
+}
+
+#[test]
+fn shortcut_maze_opens_a_shorter_path() {
+    let mut gridworld = Gridworld::shortcut_maze(1);
+    assert_eq!(gridworld.shortest_path_length(), Some(16));
+    gridworld.step(GridAction::Up);
+    assert_eq!(gridworld.shortest_path_length(), Some(10));
+}
+
+#[test]
+fn blocking_maze_blocks_the_short_path() {
+    let mut gridworld = Gridworld::blocking_maze(1);
+    assert_eq!(gridworld.shortest_path_length(), Some(10));
+    gridworld.step(GridAction::Up);
+    assert_eq!(gridworld.shortest_path_length(), Some(16));
+}
+
+#[test]
+fn scaled_maze_keeps_its_layout() {
+    assert_eq!(Gridworld::dyna_maze().shortest_path_length(), Some(14));
+    let gridworld = Gridworld::dyna_maze().scale(2);
+    assert_eq!(gridworld.get_rows(), 12);
+    assert_eq!(gridworld.get_columns(), 18);
+    assert_eq!(gridworld.get_start(), (4, 0));
+    assert_eq!(gridworld.get_cell((0, 16)), GridCell::Goal);
+    assert_eq!(gridworld.get_cell((1, 17)), GridCell::Empty);
+    assert_eq!(gridworld.get_cell((3, 5)), GridCell::Wall);
+    assert_eq!(gridworld.shortest_path_length(), Some(2 * 14));
+}
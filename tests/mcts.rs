@@ -0,0 +1,129 @@
+use rl_examples::{
+    agents::mcts::{ AgentMcts, MctsConfig, VisitMode },
+    environment::Environment,
+    environments::bandit::{ BanditType, KArmedBandit },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+const LOOP_LENGTH: usize = 4;
+
+/// Two states visited in turn, 0 - 1 - 0 - 1, with a single action. The episode ends after
+/// `LOOP_LENGTH` steps and the reward of step t is t, so every state is revisited.
+struct Loop {
+    steps: usize,
+}
+
+impl Environment for Loop {
+    type State = usize;
+    type Action = ();
+
+    fn reset(&mut self) {
+        self.steps = 0;
+    }
+    fn step(&mut self, _action: ()) -> f64 {
+        self.steps += 1;
+        self.steps as f64
+    }
+    fn get_state(&self) -> usize {
+        self.steps % 2
+    }
+    fn get_actions(&self) -> Vec<()> {
+        vec![()]
+    }
+    fn is_terminal(&self) -> bool {
+        self.steps == LOOP_LENGTH
+    }
+    fn get_number_of_possible_actions(&self) -> usize {
+        1
+    }
+    fn get_number_of_possible_states(&self) -> usize {
+        2
+    }
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        self.steps
+    }
+    fn all_possible_states(&self) -> Vec<usize> {
+        vec![0, 1]
+    }
+}
+
+type LoopAgent = AgentMcts<Loop, EpsilonGreedySelector, MemoryStore<(usize, Option<()>)>>;
+
+fn train_on_loop(visit_mode: VisitMode) -> LoopAgent {
+    let config = MctsConfig {
+        visit_mode,
+        ..MctsConfig::default()
+    };
+    let mut agent = AgentMcts::with_config(
+        Loop { steps: 0 },
+        EpsilonGreedySelector::with_seed(0.0, 0),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        config
+    );
+    Trainer::new(TrainingBudget::Episodes(3), 1).train(&mut agent);
+    agent
+}
+
+#[test]
+fn every_visit_estimates_are_sample_means_of_all_returns() {
+    let agent = train_on_loop(VisitMode::EveryVisit);
+    // returns from steps 0 to 3 are 10, 9, 7 and 4
+    assert_eq!(agent.get_q_estimate(0, ()), (10.0 + 7.0) / 2.0);
+    assert_eq!(agent.get_q_estimate(1, ()), (9.0 + 4.0) / 2.0);
+    assert_eq!(agent.get_state_value_estimate(0), (10.0 + 7.0) / 2.0);
+    assert_eq!(agent.get_state_value_estimate(1), (9.0 + 4.0) / 2.0);
+    assert_eq!(agent.get_state_visit_count(0), 6.0);
+}
+
+#[test]
+fn first_visit_estimates_are_sample_means_of_first_returns() {
+    let agent = train_on_loop(VisitMode::FirstVisit);
+    assert_eq!(agent.get_q_estimate(0, ()), 10.0);
+    assert_eq!(agent.get_q_estimate(1, ()), 9.0);
+    assert_eq!(agent.get_state_value_estimate(0), 10.0);
+    assert_eq!(agent.get_state_value_estimate(1), 9.0);
+    assert_eq!(agent.get_state_visit_count(0), 3.0);
+}
+
+/// Bandit whose first arm always pays 1 and second arm always pays nothing
+fn constant_bandit() -> KArmedBandit {
+    KArmedBandit::from_arms_with_seed(
+        vec![
+            BanditType::Custom { value: 1.0, sample: Box::new(|_| 1.0) },
+            BanditType::Custom { value: 0.0, sample: Box::new(|_| 0.0) }
+        ],
+        0
+    )
+}
+
+#[test]
+fn truncated_episodes_bootstrap_on_a_bandit() {
+    let discount = 0.5;
+    let config = MctsConfig {
+        discount,
+        max_episode_length: Some(1),
+        ..MctsConfig::default()
+    };
+    let mut agent = AgentMcts::with_config(
+        constant_bandit(),
+        EpsilonGreedySelector::with_seed(0.1, 3),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        config
+    );
+    Trainer::new(TrainingBudget::Steps(5000), 1).train(&mut agent);
+
+    // the bandit never terminates, so pulling the first arm forever is worth 1 / (1 - discount).
+    // without bootstrapping every estimate would stay at the reward of a single pull
+    let best = 1.0 / (1.0 - discount);
+    assert!((agent.get_q_estimate(0, 0) - best).abs() < 0.1);
+    assert!((agent.get_q_estimate(0, 1) - discount * best).abs() < 0.1);
+    assert!(agent.get_q_estimate(0, 0) > agent.get_q_estimate(0, 1));
+}
@@ -0,0 +1,178 @@
+use rl_examples::{
+    agents::{
+        n_step_sarsa::AgentNStepSarsa,
+        n_step_td::AgentNStepTd,
+        td_lambda::{ AgentTdLambda, TdLambdaConfig, TraceType },
+    },
+    environment::Environment,
+    environments::random_walk::RandomWalk,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+// rewards of the steps out of states 0, 1 and 2
+const REWARDS: [f64; 3] = [1.0, 2.0, 3.0];
+const STEP_SIZE: f64 = 0.5;
+const DISCOUNT: f64 = 0.9;
+const NUM_EPISODES: usize = 3;
+
+/// Deterministic episode 0 - 1 - 2 - end with a single action, paying `REWARDS`
+struct Chain {
+    position: usize,
+}
+
+impl Environment for Chain {
+    type State = usize;
+    type Action = ();
+
+    fn reset(&mut self) {
+        self.position = 0;
+    }
+    fn step(&mut self, _action: ()) -> f64 {
+        self.position += 1;
+        REWARDS[self.position - 1]
+    }
+    fn get_state(&self) -> usize {
+        self.position
+    }
+    fn get_actions(&self) -> Vec<()> {
+        vec![()]
+    }
+    fn is_terminal(&self) -> bool {
+        self.position == REWARDS.len()
+    }
+    fn get_number_of_possible_actions(&self) -> usize {
+        1
+    }
+    fn get_number_of_possible_states(&self) -> usize {
+        REWARDS.len() + 1
+    }
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        self.position
+    }
+    fn all_possible_states(&self) -> Vec<usize> {
+        (0..=REWARDS.len()).collect()
+    }
+}
+
+/// State values after `NUM_EPISODES` episodes of TD(0), updating each state as it is left
+fn td_zero_values() -> Vec<f64> {
+    let mut values = vec![0.0; REWARDS.len() + 1];
+    for _ in 0..NUM_EPISODES {
+        for (state, reward) in REWARDS.iter().enumerate() {
+            let target = reward + DISCOUNT * values[state + 1];
+            values[state] += STEP_SIZE * (target - values[state]);
+        }
+    }
+    values
+}
+
+/// State values after `NUM_EPISODES` episodes of constant-α Monte Carlo
+fn monte_carlo_values() -> Vec<f64> {
+    let mut values = vec![0.0; REWARDS.len() + 1];
+    for _ in 0..NUM_EPISODES {
+        for state in 0..REWARDS.len() {
+            let total_return: f64 = REWARDS[state..]
+                .iter()
+                .enumerate()
+                .map(|(k, reward)| DISCOUNT.powi(k as i32) * reward)
+                .sum();
+            values[state] += STEP_SIZE * (total_return - values[state]);
+        }
+    }
+    values
+}
+
+fn n_step_td_values(n: usize) -> Vec<f64> {
+    let mut agent = AgentNStepTd::new(
+        Chain { position: 0 },
+        EpsilonGreedySelector::with_seed(1.0, 0),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        n,
+        STEP_SIZE,
+        DISCOUNT
+    );
+    Trainer::new(TrainingBudget::Episodes(NUM_EPISODES), 1).train(&mut agent);
+    (0..=REWARDS.len()).map(|state| agent.get_state_value_estimate(state)).collect()
+}
+
+fn n_step_sarsa_values(n: usize) -> Vec<f64> {
+    let mut agent = AgentNStepSarsa::new(
+        Chain { position: 0 },
+        EpsilonGreedySelector::with_seed(0.0, 0),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        n,
+        STEP_SIZE,
+        DISCOUNT
+    );
+    Trainer::new(TrainingBudget::Episodes(NUM_EPISODES), 1).train(&mut agent);
+    (0..=REWARDS.len()).map(|state| agent.get_q_estimate(state, ())).collect()
+}
+
+fn assert_close(actual: &[f64], expected: &[f64]) {
+    for (a, e) in actual.iter().zip(expected) {
+        assert!((a - e).abs() < 1e-12, "{:?} != {:?}", actual, expected);
+    }
+}
+
+#[test]
+fn one_step_td_is_td_zero() {
+    assert_eq!(n_step_td_values(1), td_zero_values());
+}
+
+#[test]
+fn unbounded_n_step_td_is_constant_step_size_monte_carlo() {
+    assert_close(&n_step_td_values(usize::MAX), &monte_carlo_values());
+    // with n at least the episode length nothing is bootstrapped either
+    assert_close(&n_step_td_values(REWARDS.len()), &monte_carlo_values());
+}
+
+#[test]
+fn one_step_sarsa_matches_td_zero_with_a_single_action() {
+    // with a single action the action values follow the TD(0) state values
+    assert_eq!(n_step_sarsa_values(1), td_zero_values());
+}
+
+#[test]
+fn unbounded_n_step_sarsa_is_constant_step_size_monte_carlo() {
+    assert_close(&n_step_sarsa_values(usize::MAX), &monte_carlo_values());
+}
+
+#[test]
+fn one_step_td_matches_td_lambda_with_zero_lambda_on_a_random_walk() {
+    let trainer = Trainer::new(TrainingBudget::Episodes(100), 1);
+    let mut n_step = AgentNStepTd::new(
+        RandomWalk::nineteen_state(),
+        EpsilonGreedySelector::with_seed(1.0, 7),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        1,
+        0.1,
+        1.0
+    );
+    let mut td_lambda = AgentTdLambda::new(
+        RandomWalk::nineteen_state(),
+        EpsilonGreedySelector::with_seed(1.0, 7),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        TdLambdaConfig {
+            trace_type: TraceType::Accumulating,
+            lambda: 0.0,
+            step_size: 0.1,
+            discount: 1.0,
+        }
+    );
+    // same seed, so both follow the same walks
+    let n_step_record = trainer.train(&mut n_step);
+    let td_lambda_record = trainer.train(&mut td_lambda);
+    assert_eq!(n_step_record.rewards, td_lambda_record.rewards);
+    for state in 1..=19 {
+        assert_eq!(
+            n_step.get_state_value_estimate(state),
+            td_lambda.get_state_value_estimate(state)
+        );
+    }
+}
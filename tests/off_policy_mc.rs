@@ -0,0 +1,70 @@
+use rl_examples::{
+    agents::off_policy_mc::{ AgentOffPolicyMc, ImportanceSampling, OffPolicyMcConfig, TargetPolicy },
+    environment::Environment,
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ MemoryStore, Store },
+};
+
+/// Single decision whose action `a` ends the episode with a reward of `a + 1`
+struct OneStep {
+    done: bool,
+}
+
+impl Environment for OneStep {
+    type State = usize;
+    type Action = usize;
+
+    fn reset(&mut self) {
+        self.done = false;
+    }
+    fn step(&mut self, action: usize) -> f64 {
+        self.done = true;
+        (action + 1) as f64
+    }
+    fn get_state(&self) -> usize {
+        0
+    }
+    fn get_actions(&self) -> Vec<usize> {
+        vec![0, 1]
+    }
+    fn is_terminal(&self) -> bool {
+        self.done
+    }
+    fn get_number_of_possible_actions(&self) -> usize {
+        2
+    }
+    fn get_number_of_possible_states(&self) -> usize {
+        1
+    }
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        0
+    }
+    fn all_possible_states(&self) -> Vec<usize> {
+        vec![0]
+    }
+}
+
+#[test]
+fn weighted_importance_sampling_reproduces_the_exact_value_of_a_one_step_mdp() {
+    // the behavior policy picks either action with probability 0.5, the target always picks 1
+    let mut agent = AgentOffPolicyMc::new(
+        OneStep { done: false },
+        EpsilonGreedySelector::with_seed(1.0, 0),
+        TargetPolicy::Fixed(Box::new(|_: &usize| 1)),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        OffPolicyMcConfig {
+            sampling: ImportanceSampling::Weighted,
+            discount: 1.0,
+        }
+    );
+    for _ in 0..20 {
+        agent.run_episode();
+    }
+    // returns of the other action get a weight of 0 and the rewards are deterministic, so
+    // the weighted average is exact as soon as the target action has been taken once
+    assert_eq!(agent.get_state_value_estimate(0), 2.0);
+    assert_eq!(agent.get_q_estimate(0, 0), 1.0);
+    assert_eq!(agent.get_q_estimate(0, 1), 2.0);
+}
@@ -0,0 +1,104 @@
+use rl_examples::{
+    agents::q_learning::AgentQLearning,
+    environment::Environment,
+    environments::blackjack::{ Blackjack, BlackjackAction, BlackjackState },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+const GOAL: usize = 3;
+
+/// Deterministic chain 0 - 1 - 2 - 3. Moving right from 2 reaches the goal with reward 1,
+/// every other transition pays nothing and moving left from 0 stays in place.
+struct Chain {
+    position: usize,
+    steps: usize,
+}
+
+impl Environment for Chain {
+    type State = usize;
+    type Action = bool;
+
+    fn reset(&mut self) {
+        self.position = 0;
+        self.steps = 0;
+    }
+    fn step(&mut self, move_right: bool) -> f64 {
+        self.steps += 1;
+        if move_right {
+            self.position += 1;
+        } else {
+            self.position = self.position.saturating_sub(1);
+        }
+        if self.position == GOAL {
+            1.0
+        } else {
+            0.0
+        }
+    }
+    fn get_state(&self) -> usize {
+        self.position
+    }
+    fn get_actions(&self) -> Vec<bool> {
+        vec![false, true]
+    }
+    fn is_terminal(&self) -> bool {
+        self.position == GOAL
+    }
+    fn get_number_of_possible_actions(&self) -> usize {
+        2
+    }
+    fn get_number_of_possible_states(&self) -> usize {
+        GOAL + 1
+    }
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        self.steps
+    }
+    fn all_possible_states(&self) -> Vec<usize> {
+        (0..=GOAL).collect()
+    }
+}
+
+#[test]
+fn q_learning_converges_on_deterministic_chain() {
+    let discount = 0.9;
+    let mut agent = AgentQLearning::new(
+        Chain { position: 0, steps: 0 },
+        EpsilonGreedySelector::with_seed(0.5, 7),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        0.5,
+        discount
+    );
+    Trainer::new(TrainingBudget::Episodes(500), 1).train(&mut agent);
+
+    // optimal action values: moving right from s pays discount^(2 - s), moving left from s
+    // costs a step, landing in max(s - 1, 0) and then acting optimally
+    let right = |s: usize| discount.powi(2 - (s as i32));
+    for s in 0..GOAL {
+        let left = discount * right(s.saturating_sub(1));
+        assert!((agent.get_q_estimate(s, true) - right(s)).abs() < 1e-6);
+        assert!((agent.get_q_estimate(s, false) - left).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn q_learning_learns_to_stick_on_hard_21() {
+    let mut agent = AgentQLearning::new(
+        Blackjack::with_seed(3),
+        EpsilonGreedySelector::with_seed(0.1, 5),
+        MemoryStore::new(),
+        MemoryStore::new(),
+        0.1,
+        1.0
+    );
+    Trainer::new(TrainingBudget::Episodes(20000), 1).train(&mut agent);
+
+    let state = BlackjackState { player_sum: 21, dealer_showing: 10, usable_ace: false };
+    assert!(agent.get_q_estimate(state, BlackjackAction::Hit) < 0.0);
+    assert!(
+        agent.get_q_estimate(state, BlackjackAction::Stick) >
+            agent.get_q_estimate(state, BlackjackAction::Hit)
+    );
+}
@@ -0,0 +1,44 @@
+use rl_examples::environments::random_walk::RandomWalk;
+
+const TOLERANCE: f64 = 1e-12;
+
+fn assert_close(values: &[f64], expected: &[f64]) {
+    assert_eq!(values.len(), expected.len());
+    for (state, (value, expected)) in values.iter().zip(expected).enumerate() {
+        assert!(
+            (value - expected).abs() < TOLERANCE,
+            "value of state {} is {}, expected {}",
+            state + 1,
+            value,
+            expected
+        );
+    }
+}
+
+#[test]
+fn five_state_values_are_the_probabilities_of_ending_on_the_right() {
+    let expected: Vec<f64> = (1..=5).map(|state| (state as f64) / 6.0).collect();
+    assert_close(&RandomWalk::five_state().true_state_values(), &expected);
+}
+
+#[test]
+fn nineteen_state_values_are_linear_between_the_rewards() {
+    let expected: Vec<f64> = (1..=19).map(|state| -1.0 + (state as f64) / 10.0).collect();
+    assert_close(&RandomWalk::nineteen_state().true_state_values(), &expected);
+}
+
+#[test]
+fn rms_error_is_zero_at_the_true_values() {
+    for walk in [RandomWalk::five_state(), RandomWalk::nineteen_state()] {
+        let true_values = walk.true_state_values();
+        assert_eq!(walk.rms_error(|state| true_values[state - 1]), 0.0);
+    }
+}
+
+#[test]
+fn rms_error_of_a_constant_offset_is_the_offset() {
+    let walk = RandomWalk::five_state();
+    let true_values = walk.true_state_values();
+    let error = walk.rms_error(|state| true_values[state - 1] + 0.5);
+    assert!((error - 0.5).abs() < TOLERANCE, "{}", error);
+}
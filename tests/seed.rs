@@ -0,0 +1,61 @@
+use rl_examples::{
+    agents::{ q::AgentQ, q_learning::AgentQLearning },
+    environments::{ bandit::KArmedBandit, blackjack::Blackjack },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    seed::SeedStream,
+    store::{ MemoryStore, Store },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+/// Average reward curve of epsilon greedy bandit runs created from an experiment seed
+fn bandit_rewards(experiment_seed: u64) -> Vec<f64> {
+    let trainer = Trainer::new(TrainingBudget::Steps(500), 10);
+    let report = trainer.run(|r| {
+        let seeds = SeedStream::new(experiment_seed).child(r as u64);
+        AgentQ::new(
+            KArmedBandit::with_seed(10, seeds.child(0).seed()),
+            EpsilonGreedySelector::with_seed(0.1, seeds.child(1).seed()),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            MemoryStore::new()
+        )
+    });
+    report.average_rewards()
+}
+
+/// Average episode returns of Q-learning blackjack runs created from an experiment seed
+fn blackjack_returns(experiment_seed: u64) -> Vec<f64> {
+    let trainer = Trainer::new(TrainingBudget::Episodes(500), 5);
+    let report = trainer.run(|r| {
+        let seeds = SeedStream::new(experiment_seed).child(r as u64);
+        AgentQLearning::new(
+            Blackjack::with_seed(seeds.child(0).seed()),
+            EpsilonGreedySelector::with_seed(0.1, seeds.child(1).seed()),
+            MemoryStore::new(),
+            MemoryStore::new(),
+            0.1,
+            1.0
+        )
+    });
+    report.average_episode_returns()
+}
+
+#[test]
+fn same_seed_gives_identical_bandit_curves() {
+    assert_eq!(bandit_rewards(7), bandit_rewards(7));
+    assert_ne!(bandit_rewards(7), bandit_rewards(8));
+}
+
+#[test]
+fn same_seed_gives_identical_blackjack_curves() {
+    assert_eq!(blackjack_returns(7), blackjack_returns(7));
+    assert_ne!(blackjack_returns(7), blackjack_returns(8));
+}
+
+#[test]
+fn child_seeds_do_not_depend_on_earlier_children() {
+    let seeds = SeedStream::new(3);
+    assert_eq!(seeds.child(5), SeedStream::new(3).child(5));
+    assert_ne!(seeds.child(5).seed(), seeds.child(6).seed());
+    assert_ne!(seeds.child(5).seed(), SeedStream::new(4).child(5).seed());
+}
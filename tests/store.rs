@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use rl_examples::{
+    agents::mcts::AgentMcts,
+    environment::Environment,
+    environments::blackjack::{ Blackjack, BlackjackAction, BlackjackState },
+    selectors::epsilon_greedy::EpsilonGreedySelector,
+    store::{ store_path, DenseStore, MemoryStore, Store, StoreFormat },
+    trainer::{ Trainer, TrainingBudget },
+};
+
+const FORMATS: [StoreFormat; 2] = [StoreFormat::Json, StoreFormat::Binary];
+
+type BlackjackAgent = AgentMcts<
+    Blackjack,
+    EpsilonGreedySelector,
+    DenseStore<BlackjackState, BlackjackAction>
+>;
+
+/// Empty directory for the files of a single test
+fn scratch_directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(
+        format!("rl_examples_{}_{}", name, std::process::id())
+    );
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+fn create_agent() -> BlackjackAgent {
+    let blackjack = Blackjack::with_seed(11);
+    AgentMcts::new(
+        Blackjack::with_seed(11),
+        EpsilonGreedySelector::with_seed(0.1, 12),
+        DenseStore::from_environment(&blackjack),
+        DenseStore::from_environment(&blackjack),
+        DenseStore::from_environment(&blackjack),
+        DenseStore::from_environment(&blackjack)
+    )
+}
+
+#[test]
+fn memory_store_round_trips_in_every_format() {
+    let directory = scratch_directory("memory_store");
+    let mut store: MemoryStore<(u8, Option<bool>)> = MemoryStore::new();
+    store.store_float((0, None), 0.1);
+    store.store_float((0, Some(true)), -2.5);
+    store.store_float((3, Some(false)), 1.0 / 3.0);
+    for format in FORMATS {
+        let path = store_path(&directory, "store", format);
+        store.save(&path, format).unwrap();
+        let loaded: MemoryStore<(u8, Option<bool>)> = MemoryStore::load(&path, format).unwrap();
+        let mut entries = loaded.entries();
+        let mut expected = store.entries();
+        entries.sort_by_key(|entry| entry.0);
+        expected.sort_by_key(|entry| entry.0);
+        assert_eq!(entries, expected);
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn dense_store_round_trips_in_every_format() {
+    let directory = scratch_directory("dense_store");
+    let mut store: DenseStore<u8, bool> = DenseStore::with_states_and_actions(
+        vec![0, 1],
+        vec![false]
+    );
+    store.store_float((0, None), 0.1);
+    store.store_float((1, Some(false)), -2.5);
+    // appended after the store was sized
+    store.store_float((7, Some(true)), 1.0 / 3.0);
+    for format in FORMATS {
+        let path = store_path(&directory, "store", format);
+        store.save(&path, format).unwrap();
+        let loaded: DenseStore<u8, bool> = DenseStore::load(&path, format).unwrap();
+        for (key, value) in store.entries() {
+            assert_eq!(loaded.get_float(&key), value);
+        }
+        assert_eq!(loaded.get_number_of_states(), 3);
+        assert_eq!(loaded.get_number_of_actions(), 2);
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn dense_store_keeps_values_when_a_new_action_widens_the_rows() {
+    let mut store: DenseStore<u8, u8> = DenseStore::with_states_and_actions(vec![0, 1], vec![0]);
+    store.store_float((0, None), 1.0);
+    store.store_float((0, Some(0)), 2.0);
+    store.store_float((1, None), 3.0);
+    store.store_float((1, Some(0)), 4.0);
+    // state 1 gains an action after its row exists
+    store.store_float((1, Some(1)), 5.0);
+    assert_eq!(store.get_number_of_actions(), 2);
+    assert_eq!(store.get_float(&(0, None)), 1.0);
+    assert_eq!(store.get_float(&(0, Some(0))), 2.0);
+    assert_eq!(store.get_float(&(0, Some(1))), 0.0);
+    assert_eq!(store.get_float(&(1, None)), 3.0);
+    assert_eq!(store.get_float(&(1, Some(0))), 4.0);
+    assert_eq!(store.get_float(&(1, Some(1))), 5.0);
+    store.increment_float((0, Some(1)), 0.5);
+    assert_eq!(store.get_float(&(0, Some(1))), 0.5);
+    assert_eq!(store.get_float(&(1, Some(1))), 5.0);
+}
+
+#[test]
+fn dense_store_reads_unseen_ids_as_zero() {
+    let store: DenseStore<u8, u8> = DenseStore::with_states_and_actions(vec![0], vec![0]);
+    assert_eq!(store.get_float(&(0, Some(0))), 0.0);
+    // neither the state nor the action has a slot
+    assert_eq!(store.get_float(&(9, None)), 0.0);
+    assert_eq!(store.get_float(&(0, Some(9))), 0.0);
+    assert_eq!(store.get_float(&(9, Some(9))), 0.0);
+    assert_eq!(store.get_number_of_states(), 1);
+    assert_eq!(store.get_number_of_actions(), 1);
+}
+
+#[test]
+fn loaded_agent_matches_the_saved_one() {
+    let directory = scratch_directory("loaded_agent");
+    let trainer = Trainer::new(TrainingBudget::Episodes(2000), 1);
+    let mut agent = create_agent();
+    trainer.train(&mut agent);
+    for format in FORMATS {
+        agent.save_checkpoint(&directory, format).unwrap();
+        let mut loaded = create_agent();
+        loaded.load_checkpoint(&directory, format).unwrap();
+        for state in Blackjack::with_seed(0).all_possible_states() {
+            assert_eq!(loaded.get_state_value_estimate(state), agent.get_state_value_estimate(state));
+            assert_eq!(loaded.get_state_visit_count(state), agent.get_state_visit_count(state));
+            for action in [BlackjackAction::Hit, BlackjackAction::Stick] {
+                assert_eq!(loaded.get_q_estimate(state, action), agent.get_q_estimate(state, action));
+            }
+        }
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn resumed_training_matches_uninterrupted_training() {
+    let directory = scratch_directory("resumed_training");
+    let trainer = Trainer::new(TrainingBudget::Episodes(1000), 1);
+    for format in FORMATS {
+        let mut uninterrupted = create_agent();
+        trainer.train(&mut uninterrupted);
+        uninterrupted.save_checkpoint(&directory, format).unwrap();
+
+        // same deck and exploration, but its stores are replaced by the checkpoint, which
+        // has to restore every value exactly for the two agents to keep learning alike
+        let mut resumed = create_agent();
+        trainer.train(&mut resumed);
+        resumed.load_checkpoint(&directory, format).unwrap();
+
+        let uninterrupted_record = trainer.train(&mut uninterrupted);
+        let resumed_record = trainer.train(&mut resumed);
+        assert_eq!(resumed_record.rewards, uninterrupted_record.rewards);
+        for state in Blackjack::with_seed(0).all_possible_states() {
+            for action in [BlackjackAction::Hit, BlackjackAction::Stick] {
+                assert_eq!(
+                    resumed.get_q_estimate(state, action),
+                    uninterrupted.get_q_estimate(state, action)
+                );
+            }
+        }
+    }
+    std::fs::remove_dir_all(&directory).unwrap();
+}
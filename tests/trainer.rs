@@ -0,0 +1,137 @@
+use rl_examples::{
+    agents::agent::Agent,
+    environment::Environment,
+    trainer::{ Trainer, TrainingBudget },
+};
+
+/// Environment whose episodes last `episode_length` steps, or forever when it is `None`. Every
+/// step pays `reward`, and every other step is optimal with a regret of 0 and otherwise costs 1.
+struct Counter {
+    episode_length: Option<usize>,
+    reward: f64,
+    steps: usize,
+}
+
+impl Environment for Counter {
+    type State = usize;
+    type Action = ();
+
+    fn reset(&mut self) {
+        self.steps = 0;
+    }
+    fn step(&mut self, _action: ()) -> f64 {
+        self.steps += 1;
+        self.reward
+    }
+    fn get_state(&self) -> usize {
+        self.steps
+    }
+    fn get_actions(&self) -> Vec<()> {
+        vec![()]
+    }
+    fn is_terminal(&self) -> bool {
+        self.episode_length == Some(self.steps)
+    }
+    fn get_number_of_possible_actions(&self) -> usize {
+        1
+    }
+    fn get_number_of_possible_states(&self) -> usize {
+        self.episode_length.unwrap_or(usize::MAX)
+    }
+    fn get_total_number_of_actions_taken(&self) -> usize {
+        self.steps
+    }
+    fn all_possible_states(&self) -> Vec<usize> {
+        (0..self.get_number_of_possible_states()).collect()
+    }
+    fn is_optimal_action(&self, _action: &()) -> Option<bool> {
+        Some(self.steps.is_multiple_of(2))
+    }
+    fn regret(&self, _action: &()) -> Option<f64> {
+        Some((self.steps % 2) as f64)
+    }
+}
+
+/// Agent that counts its updates and checks what the trainer hands it
+struct CountingAgent {
+    environment: Counter,
+    updates: usize,
+}
+
+impl CountingAgent {
+    fn new(episode_length: Option<usize>, reward: f64) -> CountingAgent {
+        CountingAgent {
+            environment: Counter {
+                episode_length,
+                reward,
+                steps: 0,
+            },
+            updates: 0,
+        }
+    }
+}
+
+impl Agent for CountingAgent {
+    type State = usize;
+    type Action = ();
+    type Environment = Counter;
+
+    fn get_environment(&self) -> &Counter {
+        &self.environment
+    }
+    fn select_action(&mut self) {}
+    fn take_action(&mut self, action: ()) -> f64 {
+        self.environment.step(action)
+    }
+    fn update_estimate(&mut self, state: usize, _action: (), _reward: f64, is_terminal: bool) {
+        // the state is read before the step and terminal is judged after it
+        assert_eq!(state + 1, self.environment.steps);
+        assert_eq!(is_terminal, self.is_terminal());
+        self.updates += 1;
+    }
+    fn reset(&mut self) {
+        self.environment.reset();
+    }
+}
+
+#[test]
+fn step_budget_counts_steps_and_only_finished_episodes() {
+    let mut agent = CountingAgent::new(Some(3), 1.0);
+    let record = Trainer::new(TrainingBudget::Steps(10), 1).train(&mut agent);
+    assert_eq!(record.rewards, vec![1.0; 10]);
+    assert_eq!(record.episode_returns, vec![3.0; 3]);
+    assert_eq!(agent.updates, 10);
+    // the last episode is left unfinished
+    assert_eq!(agent.environment.steps, 1);
+}
+
+#[test]
+fn episode_budget_runs_until_enough_episodes_end() {
+    let mut agent = CountingAgent::new(Some(3), 2.0);
+    let record = Trainer::new(TrainingBudget::Episodes(4), 1).train(&mut agent);
+    assert_eq!(record.rewards.len(), 12);
+    assert_eq!(record.episode_returns, vec![6.0; 4]);
+    assert_eq!(agent.environment.steps, 0);
+}
+
+#[test]
+fn max_episode_length_cuts_off_episodes_that_never_terminate() {
+    let mut agent = CountingAgent::new(None, 1.0);
+    let trainer = Trainer::new(TrainingBudget::Episodes(2), 1).with_max_episode_length(5);
+    let record = trainer.train(&mut agent);
+    assert_eq!(record.rewards.len(), 10);
+    assert_eq!(record.episode_returns, vec![5.0, 5.0]);
+}
+
+#[test]
+fn report_averages_across_runs() {
+    let trainer = Trainer::new(TrainingBudget::Steps(4), 3);
+    let report = trainer.run(|r| CountingAgent::new(Some(2), r as f64));
+    assert_eq!(report.runs.len(), 3);
+    assert_eq!(report.average_rewards(), vec![1.0; 4]);
+    assert_eq!(report.average_episode_returns(), vec![2.0; 2]);
+    // every episode starts on an optimal step
+    assert_eq!(report.optimal_action_rates(), vec![1.0, 0.0, 1.0, 0.0]);
+    assert_eq!(report.average_regrets(), vec![0.0, 1.0, 0.0, 1.0]);
+    assert_eq!(report.cumulative_regrets(), vec![0.0, 1.0, 1.0, 2.0]);
+}
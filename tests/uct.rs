@@ -0,0 +1,78 @@
+use rand::{ rngs::StdRng, seq::SliceRandom, SeedableRng };
+use rl_examples::{
+    agents::uct::UctPlanner,
+    environment::Environment,
+    environments::tic_tac_toe::{ Mark, TicTacToe },
+};
+
+fn create_planner(seed: u64) -> UctPlanner {
+    UctPlanner::with_seed(1000, (2.0f64).sqrt(), 1.0, 9, seed)
+}
+
+fn play(game: &mut TicTacToe, cells: &[usize]) {
+    for cell in cells {
+        game.step(*cell);
+    }
+}
+
+#[test]
+fn blocks_an_immediate_loss() {
+    // X threatens the top row and O has no line of its own to complete
+    // X | X | .
+    // O | . | .
+    // . | . | .
+    let mut game = TicTacToe::new();
+    play(&mut game, &[0, 3, 1]);
+    for seed in 0..10 {
+        assert_eq!(create_planner(seed).search(&game), 2);
+    }
+}
+
+#[test]
+fn completes_its_own_line_before_blocking() {
+    // O to move can win in the middle row instead of blocking the top row
+    // X | X | .
+    // O | O | .
+    // X | . | .
+    let mut game = TicTacToe::new();
+    play(&mut game, &[0, 3, 1, 4, 6]);
+    for seed in 0..10 {
+        assert_eq!(create_planner(seed).search(&game), 5);
+    }
+}
+
+#[test]
+fn never_loses_to_a_random_player() {
+    let mut planner = create_planner(0);
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut game = TicTacToe::new();
+    for uct_player in [0, 1] {
+        for _ in 0..20 {
+            game.reset();
+            while !game.is_terminal() {
+                let action = if game.get_current_player() == uct_player {
+                    planner.search(&game)
+                } else {
+                    *game.get_actions().choose(&mut rng).unwrap()
+                };
+                game.step(action);
+            }
+            let random_mark = if uct_player == 0 { Mark::O } else { Mark::X };
+            assert_ne!(game.get_winner(), Some(random_mark));
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "cannot search from a terminal state")]
+fn searching_a_finished_game_panics() {
+    let mut game = TicTacToe::new();
+    play(&mut game, &[0, 3, 1, 4, 2]);
+    create_planner(0).search(&game);
+}
+
+#[test]
+#[should_panic(expected = "at least one simulation must be run for every move")]
+fn rejects_zero_simulations() {
+    UctPlanner::with_seed(0, (2.0f64).sqrt(), 1.0, 9, 0);
+}